@@ -1,40 +1,105 @@
 #![allow(dead_code)]
 use crate::config::AppConfig;
+use crate::metrics::Metrics;
 use crate::pubsub::ghost_publisher::GhostPublisher;
+use crate::server::admission::Admission;
+use crate::server::quota::QuotaRegistry;
+use crate::server::registry::SessionRegistry;
+use crate::server::transcript::TranscriptStore;
+use crate::tls::CertStatus;
 use sentiric_contracts::sentiric::event::v1::{
     CognitiveMapUpdatedEvent, MediaGenerationCompletedEvent,
 };
 use sentiric_contracts::sentiric::video::v1::video_gateway_service_client::VideoGatewayServiceClient;
+use std::sync::{Arc, RwLock};
 use tokio::sync::broadcast;
 use tonic::transport::Channel;
 
 #[derive(Clone)]
 pub struct AppState {
+    // Açılışta yüklenen konfigürasyon (yapısal alanlar: port, upstream, tls, events)
     pub config: AppConfig,
+    // [YENİ] Hot-reload edilebilen güncel konfigürasyon. Yeni oturumlar buradan okur.
+    live_config: Arc<RwLock<Arc<AppConfig>>>,
     pub ghost_publisher: GhostPublisher,
     // Crystalline Zihin Haritaları için
     pub cognitive_tx: broadcast::Sender<CognitiveMapUpdatedEvent>,
     // [YENİ] Medya Üretim Sonuçları için
     pub media_tx: broadcast::Sender<MediaGenerationCompletedEvent>,
-    // [YENİ] Video Gateway İstemcisi
-    pub video_client: Option<VideoGatewayServiceClient<Channel>>,
+    // [YENİ] Video Gateway İstemcisi (sertifika yenilendiğinde yeniden kurulur)
+    video_client: Arc<RwLock<Option<VideoGatewayServiceClient<Channel>>>>,
+    // [YENİ] mTLS sertifika durumu (/readyz)
+    cert_status: Arc<RwLock<CertStatus>>,
+    // [YENİ] Süreç içi sayaçlar (/metrics)
+    pub metrics: Arc<Metrics>,
+    // [YENİ] Tenant/kullanıcı/IP başına oturum kotaları
+    pub quotas: Arc<QuotaRegistry>,
+    // [YENİ] Süreç yüküne göre genel kabul denetimi (/readyz)
+    pub admission: Arc<Admission>,
+    // [YENİ] Canlı oturum kaydı (yönetim API'si)
+    pub sessions: Arc<SessionRegistry>,
+    // [YENİ] Canlı ve yakın zamanda biten oturumların transkriptleri
+    pub transcripts: Arc<TranscriptStore>,
 }
 
 impl AppState {
-    pub fn new(
-        config: AppConfig,
-        video_client: Option<VideoGatewayServiceClient<Channel>>,
-    ) -> Self {
-        let publisher = GhostPublisher::new(config.rabbitmq_url.clone(), config.tenant_id.clone());
-        let (cognitive_tx, _) = broadcast::channel(1024);
-        let (media_tx, _) = broadcast::channel(1024);
+    pub fn new(config: AppConfig) -> Self {
+        let publisher = GhostPublisher::new(
+            config.events.rabbitmq_url.clone(),
+            config.events.exchange.clone(),
+            config.events.ghost_buffer_capacity,
+            config.tenant_id.clone(),
+        );
+        let (cognitive_tx, _) = broadcast::channel(config.events.broadcast_capacity);
+        let (media_tx, _) = broadcast::channel(config.events.broadcast_capacity);
 
         Self {
+            live_config: Arc::new(RwLock::new(Arc::new(config.clone()))),
             config,
             ghost_publisher: publisher,
             cognitive_tx,
             media_tx,
-            video_client,
+            video_client: Arc::new(RwLock::new(None)),
+            cert_status: Arc::new(RwLock::new(CertStatus::default())),
+            metrics: Arc::new(Metrics::default()),
+            quotas: Arc::new(QuotaRegistry::default()),
+            admission: Arc::new(Admission::default()),
+            sessions: Arc::new(SessionRegistry::default()),
+            transcripts: Arc::new(TranscriptStore::default()),
         }
     }
+
+    pub fn current_config(&self) -> Arc<AppConfig> {
+        read_lock(&self.live_config).clone()
+    }
+
+    pub fn swap_config(&self, next: AppConfig) {
+        *write_lock(&self.live_config) = Arc::new(next);
+    }
+
+    pub fn video_client(&self) -> Option<VideoGatewayServiceClient<Channel>> {
+        read_lock(&self.video_client).clone()
+    }
+
+    pub fn set_video_client(&self, client: Option<VideoGatewayServiceClient<Channel>>) {
+        *write_lock(&self.video_client) = client;
+    }
+
+    pub fn cert_status(&self) -> CertStatus {
+        read_lock(&self.cert_status).clone()
+    }
+
+    pub fn set_cert_status(&self, status: CertStatus) {
+        *write_lock(&self.cert_status) = status;
+    }
+}
+
+// Zehirlenmiş (poisoned) kilitler panik yerine son değeri döner (No-Panic).
+fn read_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn write_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockWriteGuard<'_, T> {
+    lock.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
 }
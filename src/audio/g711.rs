@@ -0,0 +1,131 @@
+// G.711 µ-law / A-law (ITU-T G.711, Sun referans uygulaması)
+
+const ULAW_BIAS: i32 = 0x84;
+const ULAW_CLIP: i32 = 32635;
+const ALAW_SEG_END: [i32; 8] = [0x1F, 0x3F, 0x7F, 0xFF, 0x1FF, 0x3FF, 0x7FF, 0xFFF];
+
+pub fn ulaw_to_linear(byte: u8) -> i16 {
+    let u = !byte;
+    let mut t = (((u & 0x0F) as i32) << 3) + ULAW_BIAS;
+    t <<= (u & 0x70) >> 4;
+    (if u & 0x80 != 0 {
+        ULAW_BIAS - t
+    } else {
+        t - ULAW_BIAS
+    }) as i16
+}
+
+pub fn linear_to_ulaw(sample: i16) -> u8 {
+    let mut pcm = sample as i32;
+    let sign = if pcm < 0 {
+        pcm = -pcm;
+        0x80
+    } else {
+        0x00
+    };
+    pcm = pcm.min(ULAW_CLIP) + ULAW_BIAS;
+
+    let mut exponent = 7;
+    let mut mask = 0x4000;
+    while pcm & mask == 0 && exponent > 0 {
+        exponent -= 1;
+        mask >>= 1;
+    }
+    let mantissa = (pcm >> (exponent + 3)) & 0x0F;
+    !((sign | (exponent << 4) | mantissa) as u8)
+}
+
+pub fn alaw_to_linear(byte: u8) -> i16 {
+    let a = byte ^ 0x55;
+    let mut t = ((a & 0x0F) as i32) << 4;
+    let seg = (a & 0x70) >> 4;
+    match seg {
+        0 => t += 8,
+        1 => t += 0x108,
+        _ => {
+            t += 0x108;
+            t <<= seg - 1;
+        }
+    }
+    (if a & 0x80 != 0 { t } else { -t }) as i16
+}
+
+pub fn linear_to_alaw(sample: i16) -> u8 {
+    let mut pcm = (sample as i32) >> 3;
+    let mask = if pcm >= 0 {
+        0xD5
+    } else {
+        pcm = -pcm - 1;
+        0x55
+    };
+    let seg = ALAW_SEG_END
+        .iter()
+        .position(|&end| pcm <= end)
+        .unwrap_or(ALAW_SEG_END.len()) as i32;
+    if seg >= 8 {
+        return (0x7F ^ mask) as u8;
+    }
+    let mut aval = seg << 4;
+    aval |= if seg < 2 {
+        (pcm >> 1) & 0x0F
+    } else {
+        (pcm >> seg) & 0x0F
+    };
+    (aval ^ mask) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ulaw_round_trips_every_code() {
+        for byte in 0..=u8::MAX {
+            // 0x7F negatif sıfırdır; pozitif sıfır (0xFF) olarak kodlanır
+            if byte == 0x7F {
+                assert_eq!(linear_to_ulaw(ulaw_to_linear(byte)), 0xFF);
+                continue;
+            }
+            assert_eq!(
+                linear_to_ulaw(ulaw_to_linear(byte)),
+                byte,
+                "code {:#04x}",
+                byte
+            );
+        }
+    }
+
+    #[test]
+    fn alaw_round_trips_every_code() {
+        for byte in 0..=u8::MAX {
+            assert_eq!(
+                linear_to_alaw(alaw_to_linear(byte)),
+                byte,
+                "code {:#04x}",
+                byte
+            );
+        }
+    }
+
+    #[test]
+    fn companding_error_stays_within_segment_step() {
+        for sample in (i16::MIN..=i16::MAX).step_by(97) {
+            let expected = (sample as i32).clamp(-ULAW_CLIP, ULAW_CLIP);
+            // En büyük segmentte adım 1024 (µ-law) / 1024 (A-law)
+            let ulaw = ulaw_to_linear(linear_to_ulaw(sample)) as i32;
+            assert!(
+                (ulaw - expected).abs() <= 1024,
+                "µ-law {} -> {}",
+                sample,
+                ulaw
+            );
+            let alaw = alaw_to_linear(linear_to_alaw(sample)) as i32;
+            assert!(
+                (alaw - sample as i32).abs() <= 1024,
+                "A-law {} -> {}",
+                sample,
+                alaw
+            );
+        }
+    }
+}
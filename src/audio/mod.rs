@@ -0,0 +1,232 @@
+// [YENİ]: Gateway içi ses dönüştürme (codec + örnekleme hızı)
+pub mod g711;
+#[cfg(feature = "opus")]
+pub mod opus;
+pub mod resample;
+pub mod vad;
+
+use resample::{bytes_to_samples, samples_to_bytes, LinearResampler};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioCodec {
+    /// 16-bit little-endian mono PCM (pipeline'ın yerel formatı)
+    Pcm16,
+    /// G.711 µ-law
+    Pcmu,
+    /// G.711 A-law
+    Pcma,
+    Opus,
+}
+
+impl AudioCodec {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "pcm16" | "pcm" | "l16" | "linear16" => Some(Self::Pcm16),
+            "pcmu" | "ulaw" | "mulaw" | "g711u" => Some(Self::Pcmu),
+            "pcma" | "alaw" | "g711a" => Some(Self::Pcma),
+            "opus" => Some(Self::Opus),
+            _ => None,
+        }
+    }
+
+    pub fn supported() -> Vec<AudioCodec> {
+        let mut codecs = vec![Self::Pcm16, Self::Pcmu, Self::Pcma];
+        if cfg!(feature = "opus") {
+            codecs.push(Self::Opus);
+        }
+        codecs
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AudioFormat {
+    pub codec: AudioCodec,
+    pub sample_rate: u32,
+}
+
+/// Handshake sırasında istemcinin istediği formatı (`?codec=&codec_rate=`) doğrular.
+pub fn negotiate(
+    codec: Option<&str>,
+    rate: Option<&str>,
+    pipeline_rate: u32,
+) -> Result<AudioFormat, String> {
+    let codec = match codec.map(str::trim).filter(|c| !c.is_empty()) {
+        None => AudioCodec::Pcm16,
+        Some(name) => AudioCodec::parse(name)
+            .filter(|c| AudioCodec::supported().contains(c))
+            .ok_or_else(|| {
+                format!(
+                    "codec '{}' is not supported (supported: {:?}).",
+                    name,
+                    AudioCodec::supported()
+                )
+            })?,
+    };
+    let sample_rate = match rate.map(str::trim).filter(|r| !r.is_empty()) {
+        None => match codec {
+            AudioCodec::Pcm16 => pipeline_rate,
+            AudioCodec::Pcmu | AudioCodec::Pcma => 8000,
+            AudioCodec::Opus => 48000,
+        },
+        Some(r) => r
+            .parse()
+            .map_err(|_| format!("codec_rate '{}' is not a number.", r))?,
+    };
+
+    let valid = match codec {
+        AudioCodec::Pcm16 => crate::config::SUPPORTED_SAMPLE_RATES.contains(&sample_rate),
+        AudioCodec::Pcmu | AudioCodec::Pcma => sample_rate == 8000,
+        #[cfg(feature = "opus")]
+        AudioCodec::Opus => opus::SUPPORTED_RATES.contains(&sample_rate),
+        #[cfg(not(feature = "opus"))]
+        AudioCodec::Opus => false,
+    };
+    if !valid {
+        return Err(format!(
+            "codec_rate {} Hz is not valid for {:?}.",
+            sample_rate, codec
+        ));
+    }
+    Ok(AudioFormat { codec, sample_rate })
+}
+
+/// Oturum başına dönüştürme maliyeti (CPU süresi).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TranscodeStats {
+    pub inbound_chunks: u64,
+    pub outbound_chunks: u64,
+    pub inbound_cpu_us: u64,
+    pub outbound_cpu_us: u64,
+}
+
+enum Decoder {
+    Pcm16,
+    Pcmu,
+    Pcma,
+    #[cfg(feature = "opus")]
+    Opus(opus::OpusDecoder),
+}
+
+enum Encoder {
+    Pcm16,
+    Pcmu,
+    Pcma,
+    #[cfg(feature = "opus")]
+    Opus(opus::OpusEncoder),
+}
+
+pub struct SessionTranscoder {
+    client: AudioFormat,
+    passthrough_in: bool,
+    passthrough_out: bool,
+    decoder: Decoder,
+    encoder: Encoder,
+    in_resampler: LinearResampler,
+    out_resampler: LinearResampler,
+    stats: TranscodeStats,
+}
+
+impl SessionTranscoder {
+    /// `pipeline_in_rate`: STT'nin beklediği hız, `pipeline_out_rate`: TTS çıkış hızı.
+    #[cfg_attr(not(feature = "opus"), allow(unused_variables))]
+    pub fn new(
+        client: AudioFormat,
+        pipeline_in_rate: u32,
+        pipeline_out_rate: u32,
+        opus_frame_ms: u32,
+    ) -> Result<Self, String> {
+        let (decoder, encoder) = match client.codec {
+            AudioCodec::Pcm16 => (Decoder::Pcm16, Encoder::Pcm16),
+            AudioCodec::Pcmu => (Decoder::Pcmu, Encoder::Pcmu),
+            AudioCodec::Pcma => (Decoder::Pcma, Encoder::Pcma),
+            #[cfg(feature = "opus")]
+            AudioCodec::Opus => (
+                Decoder::Opus(opus::OpusDecoder::new(client.sample_rate)?),
+                Encoder::Opus(opus::OpusEncoder::new(client.sample_rate, opus_frame_ms)?),
+            ),
+            #[cfg(not(feature = "opus"))]
+            AudioCodec::Opus => return Err("opus support is not compiled in.".into()),
+        };
+        let is_pcm = client.codec == AudioCodec::Pcm16;
+        Ok(Self {
+            client,
+            passthrough_in: is_pcm && client.sample_rate == pipeline_in_rate,
+            passthrough_out: is_pcm && client.sample_rate == pipeline_out_rate,
+            decoder,
+            encoder,
+            in_resampler: LinearResampler::new(client.sample_rate, pipeline_in_rate),
+            out_resampler: LinearResampler::new(pipeline_out_rate, client.sample_rate),
+            stats: TranscodeStats::default(),
+        })
+    }
+
+    pub fn client_format(&self) -> AudioFormat {
+        self.client
+    }
+
+    pub fn stats(&self) -> &TranscodeStats {
+        &self.stats
+    }
+
+    /// İstemci `AudioChunk` -> pipeline PCM16. Boş parça (konuşma sonu işareti) aynen geçer.
+    pub fn inbound(&mut self, chunk: Vec<u8>) -> Result<Vec<u8>, String> {
+        self.stats.inbound_chunks += 1;
+        if self.passthrough_in || chunk.is_empty() {
+            return Ok(chunk);
+        }
+        let started = Instant::now();
+        let samples = match &mut self.decoder {
+            Decoder::Pcm16 => bytes_to_samples(&chunk),
+            Decoder::Pcmu => chunk.iter().map(|&b| g711::ulaw_to_linear(b)).collect(),
+            Decoder::Pcma => chunk.iter().map(|&b| g711::alaw_to_linear(b)).collect(),
+            #[cfg(feature = "opus")]
+            Decoder::Opus(dec) => dec.decode(&chunk)?,
+        };
+        let out = samples_to_bytes(&self.in_resampler.process(&samples));
+        self.stats.inbound_cpu_us += elapsed_us(started.elapsed());
+        Ok(out)
+    }
+
+    /// Pipeline PCM16 -> istemci formatı. Opus çerçevelemesi nedeniyle 0..n paket dönebilir.
+    pub fn outbound(&mut self, pcm: Vec<u8>) -> Result<Vec<Vec<u8>>, String> {
+        if self.passthrough_out {
+            self.stats.outbound_chunks += 1;
+            return Ok(vec![pcm]);
+        }
+        let started = Instant::now();
+        let samples = self.out_resampler.process(&bytes_to_samples(&pcm));
+        let packets = match &mut self.encoder {
+            Encoder::Pcm16 => vec![samples_to_bytes(&samples)],
+            Encoder::Pcmu => vec![samples.iter().map(|&s| g711::linear_to_ulaw(s)).collect()],
+            Encoder::Pcma => vec![samples.iter().map(|&s| g711::linear_to_alaw(s)).collect()],
+            #[cfg(feature = "opus")]
+            Encoder::Opus(enc) => enc.encode(&samples)?,
+        };
+        self.stats.outbound_chunks += packets.len() as u64;
+        self.stats.outbound_cpu_us += elapsed_us(started.elapsed());
+        Ok(packets)
+    }
+
+    /// Oturum içi yeniden yapılandırmada TTS çıkış hızı değişirse çağrılır.
+    pub fn set_pipeline_output_rate(&mut self, pipeline_out_rate: u32) {
+        self.passthrough_out =
+            self.client.codec == AudioCodec::Pcm16 && self.client.sample_rate == pipeline_out_rate;
+        self.out_resampler = LinearResampler::new(pipeline_out_rate, self.client.sample_rate);
+    }
+
+    /// `ClearBuffer` sonrası bekleyen (henüz kodlanmamış) TTS sesini atar.
+    pub fn reset_outbound(&mut self) {
+        self.out_resampler.reset();
+        #[cfg(feature = "opus")]
+        if let Encoder::Opus(enc) = &mut self.encoder {
+            enc.reset();
+        }
+    }
+}
+
+fn elapsed_us(d: Duration) -> u64 {
+    d.as_micros().min(u64::MAX as u128) as u64
+}
@@ -0,0 +1,77 @@
+// Opus kodlama/çözme (libopus, `opus` özelliği ile derlenir)
+use audiopus::coder::{Decoder, Encoder};
+use audiopus::packet::Packet;
+use audiopus::{Application, Channels, MutSignals, SampleRate};
+
+pub const SUPPORTED_RATES: [u32; 5] = [8000, 12000, 16000, 24000, 48000];
+
+fn sample_rate(rate: u32) -> Result<SampleRate, String> {
+    SampleRate::try_from(rate as i32).map_err(|_| format!("opus does not support {} Hz", rate))
+}
+
+pub struct OpusDecoder {
+    decoder: Decoder,
+    max_frame: usize,
+}
+
+impl OpusDecoder {
+    pub fn new(rate: u32) -> Result<Self, String> {
+        let decoder =
+            Decoder::new(sample_rate(rate)?, Channels::Mono).map_err(|e| e.to_string())?;
+        Ok(Self {
+            decoder,
+            // Opus en fazla 120 ms'lik çerçeve taşır
+            max_frame: (rate as usize * 120) / 1000,
+        })
+    }
+
+    pub fn decode(&mut self, packet: &[u8]) -> Result<Vec<i16>, String> {
+        let packet = Packet::try_from(packet).map_err(|e| e.to_string())?;
+        let mut out = vec![0i16; self.max_frame];
+        let signals = MutSignals::try_from(&mut out).map_err(|e| e.to_string())?;
+        let n = self
+            .decoder
+            .decode(Some(packet), signals, false)
+            .map_err(|e| e.to_string())?;
+        out.truncate(n);
+        Ok(out)
+    }
+}
+
+pub struct OpusEncoder {
+    encoder: Encoder,
+    frame_samples: usize,
+    pending: Vec<i16>,
+}
+
+impl OpusEncoder {
+    pub fn new(rate: u32, frame_ms: u32) -> Result<Self, String> {
+        let encoder = Encoder::new(sample_rate(rate)?, Channels::Mono, Application::Voip)
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            encoder,
+            frame_samples: (rate * frame_ms / 1000) as usize,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Tam çerçeveleri kodlar; artan örnekler bir sonraki çağrıya bekletilir.
+    pub fn encode(&mut self, samples: &[i16]) -> Result<Vec<Vec<u8>>, String> {
+        self.pending.extend_from_slice(samples);
+        let mut packets = Vec::new();
+        let mut buf = [0u8; 4000];
+        while self.pending.len() >= self.frame_samples {
+            let frame: Vec<i16> = self.pending.drain(..self.frame_samples).collect();
+            let n = self
+                .encoder
+                .encode(&frame, &mut buf)
+                .map_err(|e| e.to_string())?;
+            packets.push(buf[..n].to_vec());
+        }
+        Ok(packets)
+    }
+
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+}
@@ -0,0 +1,133 @@
+/// Akış tabanlı doğrusal örnekleme hızı dönüştürücü.
+/// Parçalar arasında son örneği ve kesirli konumu taşır; böylece sınırlarda tıkırtı oluşmaz.
+pub struct LinearResampler {
+    from: u32,
+    to: u32,
+    pos: f64,
+    last: Option<i16>,
+}
+
+impl LinearResampler {
+    pub fn new(from: u32, to: u32) -> Self {
+        Self {
+            from,
+            to,
+            pos: 0.0,
+            last: None,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.from == self.to
+    }
+
+    pub fn reset(&mut self) {
+        self.pos = 0.0;
+        self.last = None;
+    }
+
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if self.is_identity() || input.is_empty() {
+            return input.to_vec();
+        }
+        let step = self.from as f64 / self.to as f64;
+        let mut src = Vec::with_capacity(input.len() + 1);
+        if let Some(last) = self.last {
+            src.push(last);
+        }
+        src.extend_from_slice(input);
+
+        let mut out = Vec::with_capacity((input.len() as f64 / step) as usize + 1);
+        let mut pos = self.pos;
+        while pos + 1.0 < src.len() as f64 {
+            let idx = pos as usize;
+            let frac = pos - idx as f64;
+            let sample = src[idx] as f64 * (1.0 - frac) + src[idx + 1] as f64 * frac;
+            out.push(sample.round() as i16);
+            pos += step;
+        }
+
+        self.pos = pos - (src.len() - 1) as f64;
+        self.last = src.last().copied();
+        out
+    }
+}
+
+pub fn bytes_to_samples(bytes: &[u8]) -> Vec<i16> {
+    bytes
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect()
+}
+
+pub fn samples_to_bytes(samples: &[i16]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(len: usize) -> Vec<i16> {
+        (0..len).map(|i| ((i * 37) % 2000) as i16 - 1000).collect()
+    }
+
+    #[test]
+    fn chunked_output_matches_single_pass() {
+        let input = ramp(4801);
+        for (from, to) in [
+            (8000, 16000),
+            (48000, 16000),
+            (16000, 24000),
+            (44100, 16000),
+        ] {
+            let whole = LinearResampler::new(from, to).process(&input);
+            let mut chunked = Vec::new();
+            let mut resampler = LinearResampler::new(from, to);
+            // Düzensiz parça boyutları sınır durumlarını da kapsar
+            let mut rest = &input[..];
+            for size in [1, 7, 160, 3, 320, 1, 999].iter().cycle() {
+                if rest.is_empty() {
+                    break;
+                }
+                let (chunk, tail) = rest.split_at((*size).min(rest.len()));
+                chunked.extend(resampler.process(chunk));
+                rest = tail;
+            }
+            // Kesirli konum taşındığı için yalnızca yuvarlama farkı (±1) kabul edilir
+            assert_eq!(whole.len(), chunked.len(), "{} -> {}", from, to);
+            let max_diff = whole
+                .iter()
+                .zip(&chunked)
+                .map(|(a, b)| (*a as i32 - *b as i32).abs())
+                .max()
+                .unwrap_or_default();
+            assert!(max_diff <= 1, "{} -> {}: max diff {}", from, to, max_diff);
+        }
+    }
+
+    #[test]
+    fn output_length_follows_rate_ratio() {
+        let out = LinearResampler::new(8000, 16000).process(&ramp(8000));
+        assert!((out.len() as i64 - 16000).abs() <= 2, "len {}", out.len());
+        let out = LinearResampler::new(48000, 16000).process(&ramp(48000));
+        assert!((out.len() as i64 - 16000).abs() <= 2, "len {}", out.len());
+    }
+
+    #[test]
+    fn identity_passes_through_and_reset_forgets_state() {
+        let input = ramp(100);
+        assert_eq!(LinearResampler::new(16000, 16000).process(&input), input);
+
+        let mut resampler = LinearResampler::new(8000, 16000);
+        let first = resampler.process(&input);
+        resampler.reset();
+        assert_eq!(resampler.process(&input), first);
+    }
+
+    #[test]
+    fn sample_byte_conversion_round_trips() {
+        let samples = vec![i16::MIN, -1, 0, 1, i16::MAX];
+        assert_eq!(bytes_to_samples(&samples_to_bytes(&samples)), samples);
+    }
+}
@@ -0,0 +1,101 @@
+// [YENİ]: Enerji tabanlı ses aktivitesi algılama (VAD) ve TTS oynatma takibi
+use crate::config::VadConfig;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VadEvent {
+    SpeechStarted,
+    SpeechEnded,
+}
+
+pub struct EnergyVad {
+    threshold_rms: f64,
+    speech_start_samples: u64,
+    silence_samples: u64,
+    in_speech: bool,
+    speech_run: u64,
+    silence_run: u64,
+}
+
+impl EnergyVad {
+    pub fn new(cfg: &VadConfig, sample_rate: u32) -> Self {
+        let ms_to_samples = |ms: u32| (sample_rate as u64 * ms as u64) / 1000;
+        Self {
+            // dBFS -> 16-bit tam ölçeğe göre doğrusal RMS
+            threshold_rms: 32768.0 * 10f64.powf(cfg.energy_threshold_dbfs / 20.0),
+            speech_start_samples: ms_to_samples(cfg.speech_start_ms),
+            silence_samples: ms_to_samples(cfg.silence_ms),
+            in_speech: false,
+            speech_run: 0,
+            silence_run: 0,
+        }
+    }
+
+    /// Pipeline hızındaki PCM16 parçasını değerlendirir; durum değişirse olay döner.
+    pub fn process(&mut self, samples: &[i16]) -> Option<VadEvent> {
+        if samples.is_empty() {
+            return None;
+        }
+        let n = samples.len() as u64;
+        let energy: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+        let rms = (energy / samples.len() as f64).sqrt();
+
+        if rms >= self.threshold_rms {
+            self.speech_run += n;
+            self.silence_run = 0;
+            if !self.in_speech && self.speech_run >= self.speech_start_samples {
+                self.in_speech = true;
+                return Some(VadEvent::SpeechStarted);
+            }
+        } else {
+            self.silence_run += n;
+            self.speech_run = 0;
+            if self.in_speech && self.silence_run >= self.silence_samples {
+                self.in_speech = false;
+                return Some(VadEvent::SpeechEnded);
+            }
+        }
+        None
+    }
+
+    /// İstemci konuşma sonunu kendisi bildirdiğinde (control 2) durumu sıfırlar.
+    pub fn reset(&mut self) {
+        self.in_speech = false;
+        self.speech_run = 0;
+        self.silence_run = 0;
+    }
+}
+
+/// İstemciye gönderilen TTS sesinin tahmini oynatma bitiş zamanı (barge-in kararı için).
+pub struct PlaybackTracker {
+    bytes_per_sec: u64,
+    playing_until: Option<Instant>,
+}
+
+impl PlaybackTracker {
+    /// `sample_rate`: pipeline'ın PCM16 çıkış hızı
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            bytes_per_sec: sample_rate as u64 * 2,
+            playing_until: None,
+        }
+    }
+
+    pub fn on_audio_sent(&mut self, pcm_len: usize) {
+        if self.bytes_per_sec == 0 {
+            return;
+        }
+        let duration = Duration::from_micros(pcm_len as u64 * 1_000_000 / self.bytes_per_sec);
+        let now = Instant::now();
+        let start = self.playing_until.filter(|t| *t > now).unwrap_or(now);
+        self.playing_until = Some(start + duration);
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing_until.is_some_and(|t| t > Instant::now())
+    }
+
+    pub fn clear(&mut self) {
+        self.playing_until = None;
+    }
+}
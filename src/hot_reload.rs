@@ -0,0 +1,182 @@
+// [YENİ]: Konfigürasyon Hot-Reload (dosya değişikliği veya SIGHUP)
+use crate::app::AppState;
+use crate::config::AppConfig;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{error, info, warn};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+pub type LogLevelHandle = reload::Handle<EnvFilter, Registry>;
+
+pub struct ConfigReloader;
+
+impl ConfigReloader {
+    pub fn start(state: Arc<AppState>, source: Option<String>, log_handle: LogLevelHandle) {
+        tokio::spawn(async move {
+            // `[server]` yeniden başlatma gerektirir; aralık bu yüzden yalnızca başlangıçta okunur
+            let interval_secs = state.config.server.config_watch_interval_secs;
+            let mut last_modified = source.as_deref().and_then(modified_at);
+
+            #[cfg(unix)]
+            let mut sighup =
+                match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                    Ok(s) => Some(s),
+                    Err(e) => {
+                        warn!(event = "SIGHUP_HANDLER_FAIL", error = %e, "SIGHUP reload disabled.");
+                        None
+                    }
+                };
+
+            loop {
+                #[cfg(unix)]
+                let hangup = async {
+                    match sighup.as_mut() {
+                        Some(s) => {
+                            s.recv().await;
+                        }
+                        None => std::future::pending::<()>().await,
+                    }
+                };
+                #[cfg(not(unix))]
+                let hangup = std::future::pending::<()>();
+
+                let poll = async {
+                    if interval_secs == 0 || source.is_none() {
+                        std::future::pending::<()>().await;
+                    }
+                    tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+                };
+
+                tokio::select! {
+                    _ = hangup => {
+                        info!(event = "CONFIG_RELOAD_SIGNAL", "SIGHUP received. Reloading configuration.");
+                        last_modified = source.as_deref().and_then(modified_at);
+                        reload(&state, source.as_deref(), &log_handle);
+                    }
+                    _ = poll => {
+                        let current = source.as_deref().and_then(modified_at);
+                        if current != last_modified {
+                            last_modified = current;
+                            info!(event = "CONFIG_FILE_CHANGED", "Config file changed. Reloading configuration.");
+                            reload(&state, source.as_deref(), &log_handle);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Geçersiz bir konfigürasyon çalışan konfigürasyonu asla etkilemez.
+fn reload(state: &AppState, source: Option<&str>, log_handle: &LogLevelHandle) {
+    let next = match AppConfig::load_from(source) {
+        Ok(c) => c,
+        Err(e) => {
+            error!(event = "CONFIG_RELOAD_REJECTED", error = %e, "Invalid configuration. Keeping the running config.");
+            return;
+        }
+    };
+
+    // Ortam değişkenleri dosyayı her yüklemede ezer; RUST_LOG varken dosyadaki log_level etkisizdir
+    if std::env::var_os("RUST_LOG").is_some() {
+        warn!(
+            event = "CONFIG_LOG_LEVEL_FROM_ENV",
+            log_level = %next.log_level,
+            "RUST_LOG is set and overrides log_level from the config file."
+        );
+    }
+
+    let current = state.current_config();
+    let (merged, restart_required) = current.merge_reloadable(&next);
+
+    if merged.log_level != current.log_level {
+        match EnvFilter::try_new(&merged.log_level) {
+            Ok(filter) => {
+                if let Err(e) = log_handle.reload(filter) {
+                    error!(event = "CONFIG_RELOAD_REJECTED", error = %e, "Log level could not be applied. Keeping the running config.");
+                    return;
+                }
+            }
+            Err(e) => {
+                error!(event = "CONFIG_RELOAD_REJECTED", error = %e, "Invalid log level. Keeping the running config.");
+                return;
+            }
+        }
+    }
+
+    if !restart_required.is_empty() {
+        warn!(event = "CONFIG_RESTART_REQUIRED", fields = %restart_required.join(","), "Changed fields require a restart and were NOT applied.");
+    }
+
+    let changed = *current != merged;
+    state.swap_config(merged);
+    info!(
+        event = "CONFIG_RELOADED",
+        changed = changed,
+        "Configuration reloaded. New sessions use the updated values."
+    );
+}
+
+fn modified_at(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TlsConfig;
+    use std::path::PathBuf;
+    use tracing_subscriber::reload::Layer;
+
+    fn base() -> AppConfig {
+        AppConfig {
+            tenant_id: "default".into(),
+            tls: TlsConfig {
+                ca_path: "/certs/ca.crt".into(),
+                cert_path: "/certs/gateway.crt".into(),
+                key_path: "/certs/gateway.key".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn write(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "hot-reload-test-{}-{}.toml",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn reload_applies_reloadable_fields_only() {
+        let state = AppState::new(base());
+        let (_layer, handle) = Layer::<EnvFilter, Registry>::new(EnvFilter::new("info"));
+
+        let mut next = base();
+        next.admission.max_active_sessions = 7;
+        next.server.config_watch_interval_secs = 60;
+        let path = write("apply", &toml::to_string(&next).unwrap());
+        reload(&state, path.to_str(), &handle);
+        std::fs::remove_file(&path).ok();
+
+        let current = state.current_config();
+        assert_eq!(current.admission.max_active_sessions, 7);
+        assert_eq!(current.server, base().server);
+    }
+
+    #[tokio::test]
+    async fn invalid_file_keeps_the_running_config() {
+        let state = AppState::new(base());
+        let (_layer, handle) = Layer::<EnvFilter, Registry>::new(EnvFilter::new("info"));
+
+        let path = write("invalid", "tenant_id = \"\"\nunknown_field = 1\n");
+        reload(&state, path.to_str(), &handle);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(*state.current_config(), base());
+    }
+}
@@ -1,32 +1,56 @@
 mod app;
+mod audio;
 mod config;
+mod hot_reload;
+mod metrics;
 mod pubsub;
 mod server;
+mod storage;
 mod telemetry;
+mod tls;
 
 use crate::app::AppState;
 use crate::telemetry::SutsFormatter;
-use axum::{routing::get, Router};
-use sentiric_contracts::sentiric::video::v1::video_gateway_service_client::VideoGatewayServiceClient;
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use axum_server::tls_rustls::RustlsConfig;
+use serde_json::json;
 use std::io::Write;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::signal;
-use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
-use tracing::info;
-use tracing_subscriber::{fmt, prelude::*, EnvFilter, Registry};
+use tracing::{error, info};
+use tracing_subscriber::{fmt, prelude::*, reload, EnvFilter, Registry};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let check_only = config::has_flag("--check-config");
     let config = match config::AppConfig::load() {
         Ok(c) => c,
         Err(e) => {
-            let _ = writeln!(std::io::stderr(), "{{\"schema_v\":\"1.0.0\",\"severity\":\"FATAL\",\"event\":\"CONFIG_ERROR\",\"message\":\"{}\"}}", e);
+            let _ = writeln!(
+                std::io::stderr(),
+                "{}",
+                json!({"schema_v": "1.0.0", "severity": "FATAL", "event": "CONFIG_ERROR", "message": e})
+            );
             std::process::exit(1);
         }
     };
 
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    // [YENİ]: --check-config -> doğrula, efektif konfigürasyonu (gizliler maskeli) yazdır ve çık.
+    if check_only {
+        let rendered = toml::to_string_pretty(&config.masked()).map_err(|e| e.to_string())?;
+        let _ = writeln!(std::io::stdout(), "{}", rendered);
+        return Ok(());
+    }
+
+    let env_filter =
+        EnvFilter::try_new(&config.log_level).unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter_layer, log_handle) = reload::Layer::new(env_filter);
     let suts_formatter = SutsFormatter::new(
         "stream-gateway-service".to_string(),
         env!("CARGO_PKG_VERSION").to_string(),
@@ -35,14 +59,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     let subscriber = Registry::default()
-        .with(env_filter)
+        .with(filter_layer)
         .with(fmt::layer().event_format(suts_formatter));
     tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
 
-    let worker_threads: usize = std::env::var("WORKER_THREADS")
-        .unwrap_or_else(|_| "2".to_string())
-        .parse()
-        .unwrap_or(2);
+    let worker_threads = config.server.worker_threads;
     let runtime = tokio::runtime::Builder::new_multi_thread()
         .worker_threads(worker_threads)
         .enable_all()
@@ -50,46 +71,87 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .expect("Failed building the Runtime");
 
     runtime.block_on(async {
-        let port = config.port;
+        let port = config.server.port;
         let tenant_id = config.tenant_id.clone();
-        let rmq_url = config.rabbitmq_url.clone();
-
-        // [ARCH-COMPLIANCE FIX]: Video Gateway İstemcisini mTLS ile kuruyoruz
-        let video_client = async {
-            if let (Ok(ca_cert), Ok(cert), Ok(key)) = (
-                tokio::fs::read(&config.tls_ca_path).await,
-                tokio::fs::read(&config.tls_cert_path).await,
-                tokio::fs::read(&config.tls_key_path).await
-            ) {
-                let ca = Certificate::from_pem(ca_cert);
-                let identity = Identity::from_pem(cert, key);
-                let tls_config = ClientTlsConfig::new().domain_name("sentiric.cloud").ca_certificate(ca).identity(identity);
-
-                let url = std::env::var("VIDEO_GATEWAY_GRPC_URL").unwrap_or_else(|_| "https://video-gateway-service:16101".to_string());
-
-                // FIX: connect_lazy() Channel döner, Result dönmez.
-                let channel = Endpoint::from_shared(url).unwrap().tls_config(tls_config).unwrap().connect_lazy();
-                tracing::info!(event="VIDEO_CLIENT_READY", "Video Gateway client configured.");
-                return Some(VideoGatewayServiceClient::new(channel));
-            }
-            tracing::warn!(event="VIDEO_CLIENT_DISABLED", "Could not configure Video Gateway client.");
-            None
-        }.await;
-
-        let app_state = Arc::new(AppState::new(config.clone(), video_client));
-
-        crate::pubsub::consumer::CognitiveConsumer::start(rmq_url.clone(), app_state.cognitive_tx.clone()).await;
-        crate::pubsub::consumer::MediaConsumer::start(rmq_url.clone(), app_state.media_tx.clone()).await;
+        let rmq_url = config.events.rabbitmq_url.clone();
+        let exchange = config.events.exchange.clone();
+
+        let app_state = Arc::new(AppState::new(config.clone()));
+
+        // [ARCH-COMPLIANCE FIX]: Video Gateway İstemcisini mTLS ile kuruyoruz (sertifika rotasyonu izlenir)
+        crate::tls::refresh(&app_state).await;
+        crate::tls::CertWatcher::start(app_state.clone());
+        server::admission::LagProbe::start(app_state.clone());
+        crate::hot_reload::ConfigReloader::start(app_state.clone(), config::source_path(), log_handle);
+
+        crate::pubsub::consumer::CognitiveConsumer::start(rmq_url.clone(), exchange.clone(), app_state.cognitive_tx.clone()).await;
+        crate::pubsub::consumer::MediaConsumer::start(rmq_url.clone(), exchange.clone(), app_state.media_tx.clone()).await;
 
         let app = Router::new()
             .route("/healthz", get(server::http::healthz))
+            .route("/readyz", get(server::http::readyz))
+            .route("/metrics", get(server::http::metrics))
             .route("/ws", get(server::ws_handler::ws_upgrade))
+            .route("/admin/sessions", get(server::admin::list_sessions))
+            .route(
+                "/admin/sessions/:session_id",
+                get(server::admin::get_session).delete(server::admin::terminate_session),
+            )
+            .route(
+                "/admin/sessions/:session_id/listen",
+                get(server::monitor::listen),
+            )
+            .route(
+                "/admin/sessions/:session_id/supervisor",
+                post(server::supervisor::command),
+            )
+            .route(
+                "/sessions/:session_id/transcript",
+                get(server::transcript::get_transcript),
+            )
+            .route(
+                "/sessions/:session_id/captions",
+                get(server::captions::stream),
+            )
             .with_state(app_state);
 
-        let listener = TcpListener::bind(format!("0.0.0.0:{}", port)).await.unwrap();
-        info!(event = "SERVER_READY", tenant_id = %tenant_id, port = port, "Stream Gateway listening.");
-
-        axum::serve(listener, app).with_graceful_shutdown(shutdown_signal(tenant_id)).await.unwrap();
+        // [YENİ]: Yerel TLS (wss) etkinse dinleyici rustls ile sonlandırılır; aksi halde düz TCP.
+        if config.server.tls.enabled {
+            let tls_cfg = config.server.tls.clone();
+            let server_config = match server::tls_listener::build_server_config(&tls_cfg) {
+                Ok(c) => c,
+                Err(e) => {
+                    error!(event = "LISTENER_TLS_INIT_FAIL", error = %e, "Listener TLS could not be configured.");
+                    std::process::exit(1);
+                }
+            };
+            let rustls_config = RustlsConfig::from_config(Arc::new(server_config));
+            server::tls_listener::watch(
+                rustls_config.clone(),
+                tls_cfg,
+                Duration::from_secs(config.tls.watch_interval_secs),
+            );
+
+            let handle = axum_server::Handle::new();
+            let shutdown_handle = handle.clone();
+            tokio::spawn(async move {
+                shutdown_signal(tenant_id.clone()).await;
+                shutdown_handle.graceful_shutdown(Some(Duration::from_secs(10)));
+            });
+
+            let addr = SocketAddr::from(([0, 0, 0, 0], port));
+            info!(event = "SERVER_READY", tenant_id = %config.tenant_id, port = port, tls = true, "Stream Gateway listening (wss).");
+            axum_server::bind_rustls(addr, rustls_config)
+                .handle(handle)
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .unwrap();
+        } else {
+            let listener = TcpListener::bind(format!("0.0.0.0:{}", port)).await.unwrap();
+            info!(event = "SERVER_READY", tenant_id = %tenant_id, port = port, "Stream Gateway listening.");
+
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).with_graceful_shutdown(shutdown_signal(tenant_id)).await.unwrap();
+        }
     });
 
     Ok(())
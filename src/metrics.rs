@@ -0,0 +1,84 @@
+// [YENİ]: Süreç içi sayaç/gösterge kaydı; `/metrics` üzerinden Prometheus metin formatında sunulur.
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+}
+
+/// Tür + etiket kümesi -> değer
+type Family = (Kind, BTreeMap<String, f64>);
+
+#[derive(Default)]
+pub struct Metrics {
+    families: Mutex<BTreeMap<&'static str, Family>>,
+}
+
+impl Metrics {
+    pub fn incr(&self, name: &'static str, labels: &[(&str, &str)]) {
+        self.add(name, labels, 1.0);
+    }
+
+    pub fn add(&self, name: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.update(name, Kind::Counter, labels, |v| *v += value);
+    }
+
+    pub fn add_gauge(&self, name: &'static str, labels: &[(&str, &str)], delta: f64) {
+        self.update(name, Kind::Gauge, labels, |v| *v += delta);
+    }
+
+    /// Tek bir serinin güncel değeri (henüz yoksa 0).
+    pub fn gauge(&self, name: &'static str, labels: &[(&str, &str)]) -> f64 {
+        let key = series_key(labels);
+        let families = self.families.lock().unwrap_or_else(|e| e.into_inner());
+        families
+            .get(name)
+            .and_then(|(_, series)| series.get(&key))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    fn update(
+        &self,
+        name: &'static str,
+        kind: Kind,
+        labels: &[(&str, &str)],
+        apply: impl FnOnce(&mut f64),
+    ) {
+        let key = series_key(labels);
+        let mut families = self.families.lock().unwrap_or_else(|e| e.into_inner());
+        let (_, series) = families.entry(name).or_insert((kind, BTreeMap::new()));
+        apply(series.entry(key).or_insert(0.0));
+    }
+
+    pub fn render(&self) -> String {
+        let families = self.families.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+        for (name, (kind, series)) in families.iter() {
+            let kind = match kind {
+                Kind::Counter => "counter",
+                Kind::Gauge => "gauge",
+            };
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for (labels, value) in series {
+                if labels.is_empty() {
+                    let _ = writeln!(out, "{} {}", name, value);
+                } else {
+                    let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+                }
+            }
+        }
+        out
+    }
+}
+
+fn series_key(labels: &[(&str, &str)]) -> String {
+    labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, v.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect::<Vec<_>>()
+        .join(",")
+}
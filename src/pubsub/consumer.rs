@@ -11,7 +11,11 @@ use tracing::{error, info};
 pub struct CognitiveConsumer;
 
 impl CognitiveConsumer {
-    pub async fn start(rmq_url: String, tx: broadcast::Sender<CognitiveMapUpdatedEvent>) {
+    pub async fn start(
+        rmq_url: String,
+        exchange: String,
+        tx: broadcast::Sender<CognitiveMapUpdatedEvent>,
+    ) {
         tokio::spawn(async move {
             let clean_url = rmq_url.trim().replace("\"", "");
             if clean_url.is_empty() {
@@ -38,7 +42,7 @@ impl CognitiveConsumer {
                         let _ = channel
                             .queue_bind(
                                 q.name().as_str(),
-                                "sentiric_events",
+                                &exchange,
                                 "cognitive.map.updated",
                                 QueueBindOptions::default(),
                                 FieldTable::default(),
@@ -78,7 +82,11 @@ impl CognitiveConsumer {
 pub struct MediaConsumer;
 
 impl MediaConsumer {
-    pub async fn start(rmq_url: String, tx: broadcast::Sender<MediaGenerationCompletedEvent>) {
+    pub async fn start(
+        rmq_url: String,
+        exchange: String,
+        tx: broadcast::Sender<MediaGenerationCompletedEvent>,
+    ) {
         tokio::spawn(async move {
             let clean_url = rmq_url.trim().replace("\"", "");
             if clean_url.is_empty() {
@@ -106,7 +114,7 @@ impl MediaConsumer {
                         let _ = channel
                             .queue_bind(
                                 q.name().as_str(),
-                                "sentiric_events",
+                                &exchange,
                                 "media.generation.completed",
                                 QueueBindOptions::default(),
                                 FieldTable::default(),
@@ -115,7 +123,7 @@ impl MediaConsumer {
                         let _ = channel
                             .queue_bind(
                                 q.name().as_str(),
-                                "sentiric_events",
+                                &exchange,
                                 "media.generation.failed",
                                 QueueBindOptions::default(),
                                 FieldTable::default(),
@@ -8,7 +8,6 @@ use tokio::sync::Mutex;
 use tokio::time::{sleep, Duration};
 use tracing::{info, warn};
 
-const MAX_BUFFER_CAPACITY: usize = 1000;
 const MAX_BACKOFF_SECS: u64 = 60;
 
 // [ARCH-COMPLIANCE FIX]: Type Complexity hatasını gidermek için Type Alias
@@ -18,12 +17,12 @@ type SharedGhostBuffer = Arc<Mutex<VecDeque<RmqPayload>>>;
 #[derive(Clone)]
 pub struct GhostPublisher {
     buffer: SharedGhostBuffer,
+    capacity: usize,
 }
 
 impl GhostPublisher {
-    pub fn new(rabbitmq_url: String, tenant_id: String) -> Self {
-        let buffer: SharedGhostBuffer =
-            Arc::new(Mutex::new(VecDeque::with_capacity(MAX_BUFFER_CAPACITY)));
+    pub fn new(rabbitmq_url: String, exchange: String, capacity: usize, tenant_id: String) -> Self {
+        let buffer: SharedGhostBuffer = Arc::new(Mutex::new(VecDeque::with_capacity(capacity)));
         let buffer_clone = buffer.clone();
 
         tokio::spawn(async move {
@@ -55,7 +54,7 @@ impl GhostPublisher {
                                     if let Some((routing_key, content_type, payload_bytes)) = msg {
                                         let res = channel
                                             .basic_publish(
-                                                "sentiric_events",
+                                                &exchange,
                                                 &routing_key,
                                                 BasicPublishOptions::default(),
                                                 &payload_bytes,
@@ -66,7 +65,7 @@ impl GhostPublisher {
 
                                         if res.is_err() {
                                             let mut b = buffer_clone.lock().await;
-                                            if b.len() >= MAX_BUFFER_CAPACITY {
+                                            if b.len() >= capacity {
                                                 warn!(event="GHOST_BUFFER_FULL_DISCARD", tenant_id=%tenant_id, "Ring buffer full. Dropping oldest message.");
                                             } else {
                                                 b.push_front((
@@ -97,7 +96,7 @@ impl GhostPublisher {
             }
         });
 
-        Self { buffer }
+        Self { buffer, capacity }
     }
 
     pub async fn publish_json(&self, routing_key: &str, payload: Value) {
@@ -112,7 +111,7 @@ impl GhostPublisher {
 
     async fn enqueue(&self, routing_key: &str, content_type: &str, payload: Vec<u8>) {
         let mut b = self.buffer.lock().await;
-        if b.len() >= MAX_BUFFER_CAPACITY {
+        if b.len() >= self.capacity {
             let _ = b.pop_front();
         }
         b.push_back((routing_key.to_string(), content_type.to_string(), payload));
@@ -0,0 +1,135 @@
+// [YENİ]: Yönetim HTTP uçları: canlı oturumları listeleme, inceleme ve zorla sonlandırma.
+// `admin.tokens` ve `admin.principals` boşsa uçlar kapalıdır.
+use super::auth;
+use super::registry::{SessionCommand, SessionEntry};
+use crate::app::AppState;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::error::SendTimeoutError;
+use tracing::{info, warn};
+
+/// Oturum döngüsünün `Inspect` komutuna yanıt vermesi için beklenecek süre
+const INSPECT_TIMEOUT: Duration = Duration::from_secs(1);
+/// Oturumun komut kuyruğunda yer açılması için beklenecek süre
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub fn bearer(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+/// Yönetim isteğinin reddedilme sebebi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Denied {
+    /// Hiç yönetim belirteci tanımlı değil
+    Disabled,
+    Unauthorized,
+}
+
+impl IntoResponse for Denied {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Disabled => error(StatusCode::NOT_FOUND, "admin API is disabled"),
+            Self::Unauthorized => error(StatusCode::UNAUTHORIZED, "missing or invalid admin token"),
+        }
+    }
+}
+
+/// Yönetim belirtecini doğrular ve sahibini (isimli belirteçte ad, değilse parmak izi) döner.
+pub fn authenticate(state: &AppState, presented: Option<&str>) -> Result<String, Denied> {
+    let admin = state.current_config().admin.clone();
+    if admin.tokens.is_empty() && admin.principals.is_empty() {
+        return Err(Denied::Disabled);
+    }
+    presented
+        .and_then(|token| auth::named_principal(&admin.principals, &admin.tokens, token))
+        .ok_or_else(|| {
+            warn!(
+                event = "ADMIN_AUTH_FAILED",
+                "Admin request with missing or invalid token."
+            );
+            Denied::Unauthorized
+        })
+}
+
+/// Yönetim belirteci doğrulanamazsa döndürülecek hata yanıtı; geçerliyse `None`.
+pub fn unauthorized(state: &AppState, presented: Option<&str>) -> Option<Response> {
+    authenticate(state, presented)
+        .err()
+        .map(IntoResponse::into_response)
+}
+
+pub async fn list_sessions(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    if let Some(resp) = unauthorized(&state, bearer(&headers)) {
+        return resp;
+    }
+    let sessions: Vec<_> = state.sessions.list().iter().map(|s| s.summary()).collect();
+    Json(json!({ "count": sessions.len(), "sessions": sessions })).into_response()
+}
+
+pub async fn get_session(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(resp) = unauthorized(&state, bearer(&headers)) {
+        return resp;
+    }
+    let Some(entry) = state.sessions.get(&session_id) else {
+        return error(StatusCode::NOT_FOUND, "session not found");
+    };
+    let mut detail = entry.summary();
+    // Döngü meşgulse statik bilgiler yine de döner
+    detail["live"] = entry
+        .inspect(INSPECT_TIMEOUT)
+        .await
+        .unwrap_or_else(|| json!({ "pipeline": "busy" }));
+    Json(detail).into_response()
+}
+
+pub async fn terminate_session(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let principal = match authenticate(&state, bearer(&headers)) {
+        Ok(principal) => principal,
+        Err(denied) => return denied.into_response(),
+    };
+    let Some(entry) = state.sessions.get(&session_id) else {
+        return error(StatusCode::NOT_FOUND, "session not found");
+    };
+    if let Err(resp) = deliver(&entry, SessionCommand::Terminate).await {
+        return resp;
+    }
+    info!(event = "ADMIN_SESSION_TERMINATE", trace_id = %entry.info.trace_id, session_id = %session_id, principal = %principal, "Session termination requested by admin.");
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({ "session_id": session_id, "status": "terminating" })),
+    )
+        .into_response()
+}
+
+/// Komutu oturum döngüsüne iletir. Komut kuyruğu dolu kalırsa istek süresiz beklemez.
+pub async fn deliver(entry: &SessionEntry, command: SessionCommand) -> Result<(), Response> {
+    match entry.send_timeout(command, COMMAND_TIMEOUT).await {
+        Ok(()) => Ok(()),
+        Err(SendTimeoutError::Closed(_)) => Err(error(StatusCode::GONE, "session already ended")),
+        Err(SendTimeoutError::Timeout(_)) => Err(error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "session is busy, try again",
+        )),
+    }
+}
+
+pub fn error(code: StatusCode, message: &str) -> Response {
+    (code, Json(json!({ "error": message }))).into_response()
+}
@@ -0,0 +1,237 @@
+// [YENİ]: Süreç yüküne göre genel kabul denetimi. Aktif oturum, olay döngüsü gecikmesi
+// veya giden kuyruk baskısı eşikleri aşınca yeni yükseltmeler reddedilir ve `/readyz` düşer.
+use super::socket::QUEUE_DEPTH;
+use crate::app::AppState;
+use crate::config::AdmissionConfig;
+use crate::metrics::Metrics;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Ölçülen gecikme örneklerinin üstel ortalamadaki ağırlığı (tekil takılmaları yumuşatır)
+const LAG_SMOOTHING: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overload {
+    ActiveSessions,
+    EventLoopLag,
+    OutboundQueueDepth,
+}
+
+impl Overload {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ActiveSessions => "active_sessions",
+            Self::EventLoopLag => "event_loop_lag",
+            Self::OutboundQueueDepth => "outbound_queue_depth",
+        }
+    }
+}
+
+/// `/readyz` ve reddedilen yükseltme yanıtlarında gösterilen anlık durum.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdmissionState {
+    pub accepting: bool,
+    pub reason: Option<Overload>,
+    pub active_sessions: usize,
+    pub event_loop_lag_ms: u64,
+    pub outbound_queue_depth: usize,
+}
+
+#[derive(Default)]
+pub struct Admission {
+    /// Yükseltmesi kabul edilmiş, henüz bitmemiş oturumlar (handshake aşaması dahil)
+    active: AtomicUsize,
+    lag_ms: AtomicU64,
+}
+
+impl Admission {
+    pub fn state(&self, cfg: &AdmissionConfig, metrics: &Metrics) -> AdmissionState {
+        let active_sessions = self.active.load(Ordering::Relaxed);
+        let event_loop_lag_ms = self.lag_ms.load(Ordering::Relaxed);
+        let outbound_queue_depth = metrics.gauge(QUEUE_DEPTH, &[]).max(0.0) as usize;
+
+        let reason = if cfg.max_active_sessions > 0 && active_sessions >= cfg.max_active_sessions {
+            Some(Overload::ActiveSessions)
+        } else if cfg.max_event_loop_lag_ms > 0 && event_loop_lag_ms > cfg.max_event_loop_lag_ms {
+            Some(Overload::EventLoopLag)
+        } else if cfg.max_outbound_queue_depth > 0
+            && outbound_queue_depth > cfg.max_outbound_queue_depth
+        {
+            Some(Overload::OutboundQueueDepth)
+        } else {
+            None
+        };
+        AdmissionState {
+            accepting: reason.is_none(),
+            reason,
+            active_sessions,
+            event_loop_lag_ms,
+            outbound_queue_depth,
+        }
+    }
+
+    /// Eşikler aşılmamışsa oturumu sayar; bilet düşürülünce sayaç azalır.
+    pub fn try_admit(
+        self: &Arc<Self>,
+        cfg: &AdmissionConfig,
+        metrics: &Metrics,
+    ) -> Result<AdmissionTicket, AdmissionState> {
+        let state = self.state(cfg, metrics);
+        if !state.accepting {
+            return Err(state);
+        }
+        // Sınır kontrolü ve artırma tek atomik adımdır; eşzamanlı yükseltmeler sınırı aşamaz
+        let max = cfg.max_active_sessions;
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (max == 0 || active < max).then_some(active + 1)
+            })
+            .map_err(|active_sessions| AdmissionState {
+                accepting: false,
+                reason: Some(Overload::ActiveSessions),
+                active_sessions,
+                ..state
+            })?;
+        Ok(AdmissionTicket {
+            admission: self.clone(),
+        })
+    }
+}
+
+/// Oturum boyunca tutulur.
+pub struct AdmissionTicket {
+    admission: Arc<Admission>,
+}
+
+impl Drop for AdmissionTicket {
+    fn drop(&mut self) {
+        self.admission.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Çalışma zamanı iş parçacıkları doyduğunda zamanlayıcılar geç uyanır;
+/// periyodik uykunun taşma süresi olay döngüsü gecikmesi olarak ölçülür.
+pub struct LagProbe;
+
+impl LagProbe {
+    pub fn start(state: Arc<AppState>) {
+        tokio::spawn(async move {
+            let mut smoothed = 0.0;
+            let mut overloaded = false;
+            loop {
+                let cfg = state.current_config().admission.clone();
+                let interval = Duration::from_millis(cfg.lag_probe_interval_ms.max(1));
+                let started = Instant::now();
+                tokio::time::sleep(interval).await;
+                let sample = started.elapsed().saturating_sub(interval).as_secs_f64() * 1000.0;
+                smoothed = LAG_SMOOTHING * sample + (1.0 - LAG_SMOOTHING) * smoothed;
+                state
+                    .admission
+                    .lag_ms
+                    .store(smoothed.round() as u64, Ordering::Relaxed);
+
+                let admission = state.admission.state(&cfg, &state.metrics);
+                if !admission.accepting && !overloaded {
+                    warn!(
+                        event = "ADMISSION_CLOSED",
+                        reason = admission.reason.map(Overload::as_str).unwrap_or_default(),
+                        active_sessions = admission.active_sessions,
+                        event_loop_lag_ms = admission.event_loop_lag_ms,
+                        outbound_queue_depth = admission.outbound_queue_depth,
+                        "Gateway overloaded, new sessions are refused."
+                    );
+                } else if admission.accepting && overloaded {
+                    info!(
+                        event = "ADMISSION_REOPENED",
+                        "Gateway load recovered, accepting new sessions."
+                    );
+                }
+                overloaded = !admission.accepting;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn cfg(max_active_sessions: usize) -> AdmissionConfig {
+        AdmissionConfig {
+            max_active_sessions,
+            ..AdmissionConfig::default()
+        }
+    }
+
+    #[test]
+    fn active_limit_is_released_with_the_ticket() {
+        let admission = Arc::new(Admission::default());
+        let metrics = Metrics::default();
+        let cfg = cfg(2);
+        let first = admission.try_admit(&cfg, &metrics).ok().unwrap();
+        let _second = admission.try_admit(&cfg, &metrics).ok().unwrap();
+        let denied = admission.try_admit(&cfg, &metrics).err().unwrap();
+        assert_eq!(denied.reason, Some(Overload::ActiveSessions));
+        assert_eq!(denied.active_sessions, 2);
+        drop(first);
+        assert!(admission.try_admit(&cfg, &metrics).is_ok());
+    }
+
+    #[test]
+    fn concurrent_admits_never_exceed_the_limit() {
+        let admission = Arc::new(Admission::default());
+        let metrics = Arc::new(Metrics::default());
+        let cfg = cfg(5);
+        let handles: Vec<_> = (0..32)
+            .map(|_| {
+                let admission = admission.clone();
+                let metrics = metrics.clone();
+                let cfg = cfg.clone();
+                thread::spawn(move || admission.try_admit(&cfg, &metrics).ok())
+            })
+            .collect();
+        let tickets: Vec<_> = handles
+            .into_iter()
+            .filter_map(|h| h.join().unwrap())
+            .collect();
+        assert_eq!(tickets.len(), 5);
+        assert_eq!(admission.active.load(Ordering::Acquire), 5);
+        drop(tickets);
+        assert_eq!(admission.active.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn lag_and_queue_thresholds_are_opt_in() {
+        let admission = Arc::new(Admission::default());
+        let metrics = Metrics::default();
+        admission.lag_ms.store(10_000, Ordering::Relaxed);
+        metrics.add_gauge(QUEUE_DEPTH, &[], 1_000.0);
+        assert!(
+            admission
+                .state(&AdmissionConfig::default(), &metrics)
+                .accepting
+        );
+
+        let lag = AdmissionConfig {
+            max_event_loop_lag_ms: 250,
+            ..AdmissionConfig::default()
+        };
+        let state = admission.state(&lag, &metrics);
+        assert_eq!(state.reason, Some(Overload::EventLoopLag));
+        assert!(admission.try_admit(&lag, &metrics).is_err());
+
+        let queue = AdmissionConfig {
+            max_outbound_queue_depth: 500,
+            ..AdmissionConfig::default()
+        };
+        assert_eq!(
+            admission.state(&queue, &metrics).reason,
+            Some(Overload::OutboundQueueDepth)
+        );
+    }
+}
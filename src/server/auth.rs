@@ -0,0 +1,153 @@
+// [YENİ]: Belirteç karşılaştırması. Sabit zamanlı karşılaştırma, yanıt süresinden belirtecin
+// ortak önekinin tahmin edilmesini engeller.
+use crate::config::AppConfig;
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// `presented` listedeki belirteçlerden biriyle eşleşiyorsa `true`. Erken çıkılmaz; her belirteç
+/// karşılaştırılır.
+pub fn matches_any<'a>(tokens: impl IntoIterator<Item = &'a String>, presented: &str) -> bool {
+    tokens.into_iter().fold(false, |found, token| {
+        constant_time_eq(token, presented) | found
+    })
+}
+
+/// Sunulan belirtecin sahibi: eşleşen belirtecin parmak izi (`token-` + SHA-256 öneki).
+/// Belirtecin kendisi loglara ve kota anahtarlarına yazılmaz.
+pub fn principal<'a>(
+    tokens: impl IntoIterator<Item = &'a String>,
+    presented: &str,
+) -> Option<String> {
+    matches_any(tokens, presented).then(|| fingerprint(presented))
+}
+
+/// İsimli belirteçlerden (ad -> belirteç) eşleşenin adı; yoksa adsız listedeki eşleşmenin parmak izi.
+/// Tüm girdiler karşılaştırılır, erken çıkılmaz.
+pub fn named_principal<'a>(
+    named: impl IntoIterator<Item = (&'a String, &'a String)>,
+    tokens: impl IntoIterator<Item = &'a String>,
+    presented: &str,
+) -> Option<String> {
+    let name = named.into_iter().fold(None, |found, (name, token)| {
+        if constant_time_eq(token, presented) {
+            Some(name.clone())
+        } else {
+            found
+        }
+    });
+    let anonymous = principal(tokens, presented);
+    name.or(anonymous)
+}
+
+fn fingerprint(token: &str) -> String {
+    let hash = digest(&SHA256, token.as_bytes());
+    let hex: String = hash.as_ref()[..6]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("token-{}", hex)
+}
+
+/// Oturum kaynağına (transkript, altyazı) erişen isteğin sahibi.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Access {
+    /// Yönetim belirteci: tüm oturumlara erişir
+    Admin(String),
+    /// Yalnızca bu belirtecin verildiği oturuma erişir
+    Session(String),
+}
+
+impl Access {
+    pub fn resolve(cfg: &AppConfig, presented: &str) -> Self {
+        match named_principal(&cfg.admin.principals, &cfg.admin.tokens, presented) {
+            Some(principal) => Self::Admin(principal),
+            None => Self::Session(presented.to_string()),
+        }
+    }
+
+    /// Oturuma verilmiş belirteçlerden biri mi (yönetim belirteci her oturuma açılır).
+    pub fn allows<'a>(&self, session_tokens: impl IntoIterator<Item = &'a String>) -> bool {
+        match self {
+            Self::Admin(_) => true,
+            Self::Session(token) => matches_any(session_tokens, token),
+        }
+    }
+}
+
+/// Bağlantıya özel, tahmin edilemez erişim belirteci (192 bit, hex).
+pub fn session_token() -> String {
+    let mut bytes = [0u8; 24];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random source unavailable");
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Önce iki değer de SHA-256 ile sabit uzunluğa indirilir; böylece uzunluk farkı da süreye yansımaz.
+fn constant_time_eq(expected: &str, presented: &str) -> bool {
+    let expected = digest(&SHA256, expected.as_bytes());
+    let presented = digest(&SHA256, presented.as_bytes());
+    let diff = expected
+        .as_ref()
+        .iter()
+        .zip(presented.as_ref())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    std::hint::black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_only_exact_tokens() {
+        let tokens = vec!["alpha".to_string(), "bravo".to_string()];
+        assert!(matches_any(&tokens, "alpha"));
+        assert!(matches_any(&tokens, "bravo"));
+        assert!(!matches_any(&tokens, "alph"));
+        assert!(!matches_any(&tokens, "alphaa"));
+        assert!(!matches_any(&tokens, ""));
+        assert!(!matches_any(&Vec::<String>::new(), "alpha"));
+    }
+
+    #[test]
+    fn principal_is_a_stable_fingerprint() {
+        let tokens = vec!["alpha".to_string(), "bravo".to_string()];
+        let alpha = principal(&tokens, "alpha").unwrap();
+        assert_eq!(alpha, principal(&tokens, "alpha").unwrap());
+        assert_ne!(alpha, principal(&tokens, "bravo").unwrap());
+        assert!(alpha.starts_with("token-") && alpha.len() == 18);
+        assert!(!alpha.contains("alpha"));
+        assert_eq!(principal(&tokens, "charlie"), None);
+    }
+
+    #[test]
+    fn named_tokens_resolve_to_their_name() {
+        let named = std::collections::BTreeMap::from([
+            ("alice".to_string(), "a-token".to_string()),
+            ("bob".to_string(), "b-token".to_string()),
+        ]);
+        let tokens = vec!["plain".to_string()];
+        assert_eq!(
+            named_principal(&named, &tokens, "b-token").as_deref(),
+            Some("bob")
+        );
+        assert_eq!(
+            named_principal(&named, &tokens, "plain"),
+            principal(&tokens, "plain")
+        );
+        assert_eq!(named_principal(&named, &tokens, "alice"), None);
+    }
+
+    #[test]
+    fn session_tokens_are_unique_and_scoped() {
+        let a = session_token();
+        let b = session_token();
+        assert_eq!(a.len(), 48);
+        assert_ne!(a, b);
+        let issued = vec![a.clone()];
+        assert!(Access::Session(a).allows(&issued));
+        assert!(!Access::Session(b).allows(&issued));
+        assert!(Access::Admin("ops".into()).allows(&Vec::new()));
+    }
+}
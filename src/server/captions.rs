@@ -0,0 +1,347 @@
+// [YENİ]: Canlı altyazı. Transkript olaylarından kelime zamanlamalı altyazı parçaları üretilir ve
+// oturum başına SSE ucu `GET /sessions/{id}/captions` ile WebVTT veya SRT olarak yayınlanır.
+// Ara sonuç aynı kimlikli parçayla yerinde güncellenir, kesin sonuç parçayı sabitler. Dinleyici
+// yoksa parça üretilmez; yavaş dinleyici yalnızca kendi parçalarını kaybeder.
+use super::admin;
+use super::auth::Access;
+use super::transcript::{self, MIN_CUE_MS};
+use crate::app::AppState;
+use crate::metrics::Metrics;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures::stream::{self, StreamExt};
+use sentiric_contracts::sentiric::stream::v1::TranscriptEvent;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{info, warn};
+
+const LISTENERS: &str = "stream_gateway_caption_listeners";
+/// Bu olasılığın altındaki kelimeler WebVTT'de `<c.low>` sınıfıyla işaretlenir
+const LOW_CONFIDENCE: f32 = 0.5;
+
+#[derive(Debug)]
+pub struct Cue {
+    /// Oturum içinde artan sıra; ara ve kesin sürümler aynı numarayı taşır
+    pub index: u32,
+    /// Konuşmacı (`user`, `ai` ...)
+    pub track: String,
+    pub is_final: bool,
+    /// Oturum başından itibaren milisaniye
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// (kelime, başlangıç ms, düşük güven)
+    pub words: Vec<(String, u64, bool)>,
+}
+
+impl Cue {
+    pub fn vtt(&self) -> String {
+        let body = if self.words.is_empty() {
+            transcript::vtt_escape(&self.text)
+        } else {
+            let mut body = String::new();
+            for (i, (word, at, low)) in self.words.iter().enumerate() {
+                if i > 0 {
+                    body.push(' ');
+                    // Zaman etiketi parçanın içinde ve başlangıçtan sonra olmalı
+                    if *at > self.start_ms && *at < self.end_ms {
+                        let _ = write!(body, "<{}>", transcript::clock(*at, Some('.')));
+                    }
+                }
+                let word = transcript::vtt_escape(word);
+                if *low {
+                    let _ = write!(body, "<c.low>{}</c>", word);
+                } else {
+                    body.push_str(&word);
+                }
+            }
+            body
+        };
+        format!(
+            "{}-{}\n{} --> {}\n<v {}>{}",
+            self.track,
+            self.index,
+            transcript::clock(self.start_ms, Some('.')),
+            transcript::clock(self.end_ms, Some('.')),
+            transcript::vtt_escape(&self.track),
+            body
+        )
+    }
+
+    pub fn srt(&self) -> String {
+        format!(
+            "{}\n{} --> {}\n{}: {}",
+            self.index,
+            transcript::clock(self.start_ms, Some(',')),
+            transcript::clock(self.end_ms, Some(',')),
+            self.track,
+            self.text
+        )
+    }
+}
+
+/// Oturum başına altyazı kanalı. Klonlanabilir; tüm klonlar aynı kanala yazar.
+#[derive(Clone)]
+pub struct CaptionFeed {
+    tx: broadcast::Sender<Arc<Cue>>,
+}
+
+impl CaptionFeed {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self { tx }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.tx.receiver_count() > 0
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<Cue>> {
+        self.tx.subscribe()
+    }
+}
+
+/// Oturum döngüsünde transkriptleri altyazı parçalarına dönüştürür.
+pub struct Captioner {
+    feed: CaptionFeed,
+    started: Instant,
+    last_index: u32,
+    /// Konuşmacı başına açık parça: (numara, başlangıç ms)
+    open: HashMap<String, (u32, u64)>,
+}
+
+impl Captioner {
+    pub fn new(feed: CaptionFeed) -> Self {
+        Self {
+            feed,
+            started: Instant::now(),
+            last_index: 0,
+            open: HashMap::new(),
+        }
+    }
+
+    /// Parça zamanları oturum saatine göredir: bitiş olayın geldiği an, süre kelime zamanlarından.
+    pub fn observe(&mut self, event: &TranscriptEvent) {
+        let now_ms = self.started.elapsed().as_millis() as u64;
+        let first_start = event.words.first().map(|w| w.start).unwrap_or_default();
+        let spoken_ms = match event.words.last() {
+            Some(last) if last.end > first_start => ((last.end - first_start) * 1000.0) as u64,
+            _ => 0,
+        };
+        let text = transcript::single_line(&event.text);
+        let (index, start_ms, was_open) = match self.open.get(&event.sender) {
+            Some(&(index, start_ms)) => (index, start_ms, true),
+            None => {
+                if text.is_empty() {
+                    return;
+                }
+                self.last_index += 1;
+                (self.last_index, now_ms.saturating_sub(spoken_ms), false)
+            }
+        };
+        if event.is_final {
+            self.open.remove(&event.sender);
+        } else if !was_open {
+            self.open.insert(event.sender.clone(), (index, start_ms));
+        }
+        if !self.feed.is_active() {
+            return;
+        }
+        let end_ms = now_ms.max(start_ms + MIN_CUE_MS);
+        let words = event
+            .words
+            .iter()
+            .filter(|w| !w.word.trim().is_empty())
+            .map(|w| {
+                let offset = ((w.start - first_start).max(0.0) * 1000.0) as u64;
+                (
+                    w.word.trim().to_string(),
+                    (start_ms + offset).min(end_ms),
+                    w.probability > 0.0 && w.probability < LOW_CONFIDENCE,
+                )
+            })
+            .collect();
+        // Açık parçanın boş kesin sonucu da gönderilir; istemci ara parçayı siler
+        let _ = self.feed.tx.send(Arc::new(Cue {
+            index,
+            track: event.sender.clone(),
+            is_final: event.is_final,
+            start_ms,
+            end_ms,
+            text,
+            words,
+        }));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    WebVtt,
+    Srt,
+}
+
+/// Dinleyici bağlantısı kapanınca gösterge düşer.
+struct ListenerGuard(Arc<Metrics>);
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        self.0.add_gauge(LISTENERS, &[], -1.0);
+    }
+}
+
+/// `GET /sessions/{session_id}/captions?format=vtt|srt&track=<konuşmacı>` — SSE altyazı akışı.
+/// Olaylar: `header` (yalnızca WebVTT), `interim`, `final`, `lagged`, `end`.
+/// `EventSource` başlık gönderemediği için belirteç `?token=` ile de verilebilir. Yalnızca yönetim
+/// belirteci ya da oturuma `SESSION_ACCESS` ile verilen belirteç kabul edilir.
+pub async fn stream(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    let live_config = state.current_config();
+    let Some(token) = admin::bearer(&headers).or_else(|| params.get("token").map(String::as_str))
+    else {
+        warn!(
+            event = "CAPTIONS_AUTH_FAILED",
+            "Caption request without a token."
+        );
+        return admin::error(StatusCode::UNAUTHORIZED, "missing token");
+    };
+    let access = Access::resolve(&live_config, token);
+    let format = match params
+        .get("format")
+        .map(|f| f.to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("vtt") | Some("webvtt") => Format::WebVtt,
+        Some("srt") => Format::Srt,
+        Some(_) => return admin::error(StatusCode::BAD_REQUEST, "format must be vtt or srt"),
+    };
+    let track = params
+        .get("track")
+        .filter(|t| !t.is_empty() && t.as_str() != "all")
+        .cloned();
+    // Başka oturumun belirteci, olmayan oturumla aynı yanıtı alır
+    let Some(entry) = state
+        .sessions
+        .get(&session_id)
+        .filter(|entry| entry.grants(&access))
+    else {
+        return admin::error(StatusCode::NOT_FOUND, "session not found");
+    };
+    let Some(feed) = entry.captions.as_ref() else {
+        return admin::error(
+            StatusCode::NOT_FOUND,
+            "captions are disabled for this session",
+        );
+    };
+    let rx = feed.subscribe();
+    info!(event = "CAPTIONS_ATTACHED", trace_id = %entry.info.trace_id, session_id = %session_id, "Caption listener attached.");
+    drop(entry);
+
+    state.metrics.add_gauge(LISTENERS, &[], 1.0);
+    let guard = ListenerGuard(state.metrics.clone());
+    let header =
+        (format == Format::WebVtt).then(|| Event::default().event("header").data("WEBVTT"));
+    let cues = stream::unfold(Some((rx, guard)), move |listener| {
+        let track = track.clone();
+        async move {
+            let (mut rx, guard) = listener?;
+            loop {
+                let event = match rx.recv().await {
+                    Ok(cue) => {
+                        if track.as_ref().is_some_and(|t| *t != cue.track) {
+                            continue;
+                        }
+                        let data = match format {
+                            Format::WebVtt => cue.vtt(),
+                            Format::Srt => cue.srt(),
+                        };
+                        Event::default()
+                            .event(if cue.is_final { "final" } else { "interim" })
+                            .data(data)
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        Event::default().event("lagged").data(skipped.to_string())
+                    }
+                    Err(RecvError::Closed) => {
+                        let end = Event::default().event("end").data("session ended");
+                        return Some((Ok::<_, Infallible>(end), None));
+                    }
+                };
+                return Some((Ok(event), Some((rx, guard))));
+            }
+        }
+    });
+    Sse::new(stream::iter(header.map(Ok)).chain(cues))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(words: Vec<(&str, u64, bool)>) -> Cue {
+        Cue {
+            index: 3,
+            track: "user".into(),
+            is_final: true,
+            start_ms: 1_000,
+            end_ms: 2_000,
+            text: words
+                .iter()
+                .map(|(w, _, _)| *w)
+                .collect::<Vec<_>>()
+                .join(" "),
+            words: words
+                .into_iter()
+                .map(|(w, at, low)| (w.to_string(), at, low))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn vtt_places_timestamp_tags_inside_the_cue() {
+        // İlk kelimeye, başlangıca denk gelene ve bitişe taşana etiket konmaz
+        let cue = cue(vec![
+            ("merhaba", 1_000, false),
+            ("nasıl", 1_000, false),
+            ("yardımcı", 1_450, false),
+            ("olabilirim", 2_000, false),
+        ]);
+        assert_eq!(
+            cue.vtt(),
+            "user-3\n00:00:01.000 --> 00:00:02.000\n\
+             <v user>merhaba nasıl <00:00:01.450>yardımcı olabilirim"
+        );
+    }
+
+    #[test]
+    fn vtt_marks_low_confidence_words_and_escapes() {
+        let cue = cue(vec![("a<b", 1_000, true), ("tamam", 1_500, true)]);
+        assert_eq!(
+            cue.vtt(),
+            "user-3\n00:00:01.000 --> 00:00:02.000\n\
+             <v user><c.low>a&lt;b</c> <00:00:01.500><c.low>tamam</c>"
+        );
+    }
+
+    #[test]
+    fn vtt_falls_back_to_text_without_words() {
+        let mut cue = cue(Vec::new());
+        cue.text = "R&D".into();
+        assert_eq!(
+            cue.vtt(),
+            "user-3\n00:00:01.000 --> 00:00:02.000\n<v user>R&amp;D"
+        );
+        assert_eq!(cue.srt(), "3\n00:00:01,000 --> 00:00:02,000\nuser: R&D");
+    }
+}
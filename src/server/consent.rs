@@ -0,0 +1,209 @@
+// [YENİ]: Arayan onayı ve kayıt bilgilendirmesi. Onay handshake'te (`?consent=granted`) ya da
+// `grant_consent` / `decline_consent` kontrol sinyalleriyle verilir; o zamana kadar oturum
+// yalnızca dinler, hiçbir şey işlenmez veya saklanmaz.
+use super::control::ControlEvent;
+use crate::audio::resample::{bytes_to_samples, samples_to_bytes, LinearResampler};
+use crate::config::ConsentConfig;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Anons gerçek zamanın bu kadar önünde gönderilir; giden kuyruk taşmaz, istemci tamponu boşalmaz
+const DISCLOSURE_LEAD: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentState {
+    /// Tenant politikası onay istemiyor
+    NotRequired,
+    Pending,
+    Granted,
+    Declined,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Consent {
+    pub required: bool,
+    pub state: ConsentState,
+    /// `handshake` veya `control`
+    pub source: Option<&'static str>,
+    pub decided_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    deadline: Option<Instant>,
+}
+
+impl Consent {
+    pub fn new(cfg: &ConsentConfig, granted_at_handshake: bool) -> Self {
+        let mut consent = Self {
+            required: cfg.required,
+            state: if cfg.required {
+                ConsentState::Pending
+            } else {
+                ConsentState::NotRequired
+            },
+            source: None,
+            decided_at: None,
+            deadline: None,
+        };
+        if granted_at_handshake {
+            consent.decide(true, "handshake");
+        }
+        consent
+    }
+
+    pub fn allows_processing(&self) -> bool {
+        matches!(
+            self.state,
+            ConsentState::NotRequired | ConsentState::Granted
+        )
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.state == ConsentState::Pending
+    }
+
+    /// Yalnızca bekleyen onay değişir; değiştiyse `true`.
+    pub fn decide(&mut self, granted: bool, source: &'static str) -> bool {
+        if !self.is_pending() {
+            return false;
+        }
+        self.state = if granted {
+            ConsentState::Granted
+        } else {
+            ConsentState::Declined
+        };
+        self.source = Some(source);
+        self.decided_at = Some(Utc::now());
+        self.deadline = None;
+        true
+    }
+
+    /// Onay süresini başlatır (anons bittiğinde ya da anons yoksa hemen).
+    pub fn arm(&mut self, timeout_secs: u64) {
+        if self.is_pending() && timeout_secs > 0 {
+            self.deadline = Some(Instant::now() + Duration::from_secs(timeout_secs));
+        }
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+}
+
+/// Onay süresi dolunca tamamlanır; süre başlatılmadıysa hiç tamamlanmaz.
+pub async fn expired(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// İstemciye gönderilen onay isteği.
+pub fn required_message(cfg: &ConsentConfig, disclosure: Option<&Disclosure>) -> Value {
+    json!({
+        "type": "CONSENT_REQUIRED",
+        "disclosure": cfg.disclosure_text,
+        "disclosure_audio_secs": disclosure.map(Disclosure::duration_secs),
+        "timeout_secs": cfg.timeout_secs,
+        "controls": {
+            "grant": ControlEvent::GrantConsent.code(),
+            "decline": ControlEvent::DeclineConsent.code(),
+        },
+    })
+}
+
+/// Oturum başında istemciye çalınan bilgilendirme anonsu.
+pub struct Disclosure {
+    /// Pipeline çıkış hızında PCM16 mono
+    pcm: Vec<u8>,
+    rate: u32,
+    sent: usize,
+    started: Option<Instant>,
+}
+
+impl Disclosure {
+    pub async fn load(path: &str, output_rate: u32) -> Result<Self, String> {
+        let raw = tokio::fs::read(path)
+            .await
+            .map_err(|e| format!("disclosure audio '{}': {}", path, e))?;
+        let (samples, rate) =
+            parse_wav(&raw).map_err(|e| format!("disclosure audio '{}': {}", path, e))?;
+        let samples = LinearResampler::new(rate, output_rate).process(&samples);
+        Ok(Self {
+            pcm: samples_to_bytes(&samples),
+            rate: output_rate,
+            sent: 0,
+            started: None,
+        })
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.pcm.len() as f64 / 2.0 / self.rate as f64
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.sent >= self.pcm.len()
+    }
+
+    /// Gerçek zamana göre gönderilmesi gereken sıradaki parça; gerekmiyorsa `None`.
+    pub fn next_chunk(&mut self) -> Option<Vec<u8>> {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        let due_secs = (started.elapsed() + DISCLOSURE_LEAD).as_secs_f64();
+        let due = ((due_secs * self.rate as f64) as usize * 2).min(self.pcm.len());
+        if due <= self.sent {
+            return None;
+        }
+        let chunk = self.pcm[self.sent..due].to_vec();
+        self.sent = due;
+        Some(chunk)
+    }
+}
+
+/// 16-bit PCM WAV (mono veya stereo; stereo kanallar ortalanır).
+fn parse_wav(raw: &[u8]) -> Result<(Vec<i16>, u32), String> {
+    if raw.len() < 12 || &raw[0..4] != b"RIFF" || &raw[8..12] != b"WAVE" {
+        return Err("not a RIFF/WAVE file".into());
+    }
+    let mut format: Option<(u16, u16, u32, u16)> = None;
+    let mut pos = 12;
+    while pos + 8 <= raw.len() {
+        let id = &raw[pos..pos + 4];
+        let len =
+            u32::from_le_bytes([raw[pos + 4], raw[pos + 5], raw[pos + 6], raw[pos + 7]]) as usize;
+        let body = &raw[pos + 8..(pos + 8 + len).min(raw.len())];
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                format = Some((
+                    u16::from_le_bytes([body[0], body[1]]),
+                    u16::from_le_bytes([body[2], body[3]]),
+                    u32::from_le_bytes([body[4], body[5], body[6], body[7]]),
+                    u16::from_le_bytes([body[14], body[15]]),
+                ));
+            }
+            b"data" => {
+                let Some((1, channels @ (1 | 2), rate, 16)) = format else {
+                    return Err("only 16-bit PCM mono/stereo is supported".into());
+                };
+                let samples = bytes_to_samples(body);
+                let samples = if channels == 2 {
+                    samples
+                        .chunks_exact(2)
+                        .map(|lr| ((lr[0] as i32 + lr[1] as i32) / 2) as i16)
+                        .collect()
+                } else {
+                    samples
+                };
+                if rate == 0 {
+                    return Err("sample rate is 0".into());
+                }
+                return Ok((samples, rate));
+            }
+            _ => {}
+        }
+        // RIFF parçaları çift bayta hizalıdır
+        pos += 8 + len + (len & 1);
+    }
+    Err("no data chunk".into())
+}
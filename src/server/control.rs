@@ -0,0 +1,113 @@
+// [YENİ]: İstemci kontrol sinyalleri sözlüğü (ControlSignal.event kodları)
+use serde_json::{json, Value};
+
+/// `ControlSignal.event` değerleri. Kodlar istemcilerle sözleşmedir; değiştirilmemeli, yalnızca eklenmeli.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlEvent {
+    Interrupt,
+    EndOfUtterance,
+    Mute,
+    Unmute,
+    PausePipeline,
+    ResumePipeline,
+    SwitchLanguage,
+    SwitchVoice,
+    Ping,
+    EndSession,
+    /// [YENİ]: Arayan kayıt/AI işleme onayı verdi
+    GrantConsent,
+    DeclineConsent,
+}
+
+impl ControlEvent {
+    pub const ALL: [ControlEvent; 12] = [
+        Self::Interrupt,
+        Self::EndOfUtterance,
+        Self::Mute,
+        Self::Unmute,
+        Self::PausePipeline,
+        Self::ResumePipeline,
+        Self::SwitchLanguage,
+        Self::SwitchVoice,
+        Self::Ping,
+        Self::EndSession,
+        Self::GrantConsent,
+        Self::DeclineConsent,
+    ];
+
+    pub fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.code() == code)
+    }
+
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Interrupt => 1,
+            Self::EndOfUtterance => 2,
+            Self::Mute => 3,
+            Self::Unmute => 4,
+            Self::PausePipeline => 5,
+            Self::ResumePipeline => 6,
+            Self::SwitchLanguage => 7,
+            Self::SwitchVoice => 8,
+            Self::Ping => 9,
+            Self::EndSession => 10,
+            Self::GrantConsent => 11,
+            Self::DeclineConsent => 12,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Interrupt => "interrupt",
+            Self::EndOfUtterance => "end_of_utterance",
+            Self::Mute => "mute",
+            Self::Unmute => "unmute",
+            Self::PausePipeline => "pause_pipeline",
+            Self::ResumePipeline => "resume_pipeline",
+            Self::SwitchLanguage => "switch_language",
+            Self::SwitchVoice => "switch_voice",
+            Self::Ping => "ping",
+            Self::EndSession => "end_session",
+            Self::GrantConsent => "grant_consent",
+            Self::DeclineConsent => "decline_consent",
+        }
+    }
+
+    /// `ControlSignal` yalnızca kod taşır; değer gerektiren kontroller metin komutuyla gönderilir.
+    pub fn text_command(self) -> Option<&'static str> {
+        match self {
+            Self::SwitchLanguage => Some("[CMD:SWITCH_LANGUAGE]"),
+            Self::SwitchVoice => Some("[CMD:SWITCH_VOICE]"),
+            _ => None,
+        }
+    }
+}
+
+/// `[CMD:SWITCH_LANGUAGE]tr-TR` gibi değerli kontrol komutlarını çözer.
+pub fn parse_text_control(text: &str) -> Option<(ControlEvent, String)> {
+    ControlEvent::ALL.into_iter().find_map(|event| {
+        let prefix = event.text_command()?;
+        text.strip_prefix(prefix)
+            .map(|value| (event, value.trim().to_string()))
+    })
+}
+
+/// Handshake sonrası istemciye bildirilen, bu gateway sürümünün desteklediği kontroller.
+pub fn capabilities() -> Value {
+    let controls: Vec<Value> = ControlEvent::ALL
+        .into_iter()
+        .map(|e| {
+            json!({
+                "code": e.code(),
+                "name": e.name(),
+                "text_command": e.text_command(),
+            })
+        })
+        .collect();
+    json!({
+        "type": "CAPABILITIES",
+        "gateway_version": env!("CARGO_PKG_VERSION"),
+        "controls": controls,
+        "text_commands": ["[CMD:GENERATE_VIDEO]", "[CMD:SWITCH_LANGUAGE]", "[CMD:SWITCH_VOICE]"],
+    })
+}
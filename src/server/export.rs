@@ -0,0 +1,581 @@
+// [YENİ]: Oturum yapıtlarının (arayan/ajan sesi, transkriptler, olaylar) çağrı sürerken nesne
+// depolamaya akıtılması. Oturum döngüsü yalnızca kuyruğa ekler; tampon `part_size_bytes`'a
+// ulaştıkça ayrı görevde parça olarak yüklenir, oturum sonunda yüklemeler tamamlanır.
+use super::recording::{sanitize, Track};
+use crate::audio::resample::{bytes_to_samples, samples_to_bytes, LinearResampler};
+use crate::config::ExportConfig;
+use crate::storage::{ObjectStore, Upload};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+const CALLER_AUDIO: usize = 0;
+const AGENT_AUDIO: usize = 1;
+const TRANSCRIPTS: usize = 2;
+const EVENTS: usize = 3;
+
+enum Item {
+    Audio {
+        track: Track,
+        at: Duration,
+        pcm: Vec<u8>,
+        rate: u32,
+    },
+    /// İstemci tamponu temizlendi; henüz çalınmamış AI sesi yapıta girmez
+    ClearAi { at: Duration },
+    /// JSONL satırı; `t_ms` oturum başlangıcına göredir
+    Line {
+        artifact: usize,
+        t_ms: u64,
+        value: Value,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactStatus {
+    Completed,
+    Failed,
+    /// Hiç veri gelmedi; nesne yazılmadı
+    Empty,
+}
+
+/// Ham ses yapıtlarının biçimi. Parçalar çağrı sürerken yüklendiği için nesnede başlık yoktur;
+/// uzunluk ve biçim manifestten okunur.
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioFormat {
+    /// İşaretli 16-bit, little-endian PCM
+    pub encoding: &'static str,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub samples: u64,
+    pub duration_secs: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactSummary {
+    pub name: &'static str,
+    pub location: String,
+    pub bytes: u64,
+    pub parts: u32,
+    pub status: ArtifactStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `manifest.json` içeriği ve `export.completed` olayının gövdesi.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSummary {
+    pub session_id: String,
+    pub trace_id: String,
+    pub tenant_id: String,
+    pub sink: &'static str,
+    pub root: String,
+    pub sample_rate: u32,
+    pub started_at: String,
+    pub ended_at: String,
+    pub artifacts: Vec<ArtifactSummary>,
+    /// Kuyruk dolduğu için atılan öğeler
+    pub dropped: u64,
+}
+
+impl ExportSummary {
+    /// `completed`, `partial` (bazı yapıtlar yüklenemedi) veya `failed`.
+    pub fn result(&self) -> &'static str {
+        let failed = self
+            .artifacts
+            .iter()
+            .filter(|a| a.status == ArtifactStatus::Failed)
+            .count();
+        let written = self
+            .artifacts
+            .iter()
+            .filter(|a| a.status == ArtifactStatus::Completed)
+            .count();
+        match (failed, written) {
+            (0, _) => "completed",
+            (_, 0) => "failed",
+            _ => "partial",
+        }
+    }
+}
+
+/// Oturum döngüsü tarafı. Tüm çağrılar bloklamaz.
+pub struct Exporter {
+    tx: mpsc::Sender<Item>,
+    started: Instant,
+    dropped: Arc<AtomicU64>,
+    task: JoinHandle<ExportSummary>,
+}
+
+impl Exporter {
+    pub fn start(
+        cfg: &ExportConfig,
+        tenant_id: &str,
+        session_id: &str,
+        trace_id: &str,
+    ) -> Result<Self, String> {
+        let store = ObjectStore::from_config(cfg)?;
+        let now = chrono::Utc::now();
+        let mut root = format!(
+            "{}/{}/{}-{}",
+            sanitize(tenant_id),
+            now.format("%Y-%m-%d"),
+            sanitize(session_id),
+            now.format("%Y%m%dT%H%M%S")
+        );
+        let prefix = cfg.prefix.trim_matches('/');
+        if !prefix.is_empty() {
+            root = format!("{}/{}", prefix, root);
+        }
+
+        let artifact = |name: &'static str, content_type: &'static str| Artifact {
+            name,
+            key: format!("{}/{}", root, name),
+            content_type,
+            buffer: Vec::new(),
+            upload: None,
+            payload: 0,
+            bytes: 0,
+            parts: 0,
+            error: None,
+            resampler: None,
+            audio_rate: None,
+        };
+        let mut artifacts = [
+            artifact("caller.pcm", "application/octet-stream"),
+            artifact("agent.pcm", "application/octet-stream"),
+            artifact("transcripts.jsonl", "application/x-ndjson"),
+            artifact("events.jsonl", "application/x-ndjson"),
+        ];
+        for audio in &mut artifacts[CALLER_AUDIO..=AGENT_AUDIO] {
+            audio.audio_rate = Some(cfg.sample_rate);
+        }
+
+        let dropped = Arc::new(AtomicU64::new(0));
+        let uploader = Uploader {
+            summary: ExportSummary {
+                session_id: session_id.to_string(),
+                trace_id: trace_id.to_string(),
+                tenant_id: tenant_id.to_string(),
+                sink: store.kind(),
+                root: store.location(&root),
+                sample_rate: cfg.sample_rate,
+                started_at: now.to_rfc3339(),
+                ended_at: String::new(),
+                artifacts: Vec::new(),
+                dropped: 0,
+            },
+            manifest_key: format!("{}/manifest.json", root),
+            store,
+            artifacts,
+            playout: Playout::default(),
+            part_size: cfg.part_size_bytes.max(1),
+            sample_rate: cfg.sample_rate,
+            dropped: dropped.clone(),
+        };
+
+        let (tx, rx) = mpsc::channel(cfg.queue_capacity.max(1));
+        let task = tokio::spawn(uploader.run(rx));
+        Ok(Self {
+            tx,
+            started: Instant::now(),
+            dropped,
+            task,
+        })
+    }
+
+    pub fn audio(&mut self, track: Track, pcm: &[u8], rate: u32) {
+        if pcm.is_empty() {
+            return;
+        }
+        self.push(Item::Audio {
+            track,
+            at: self.started.elapsed(),
+            pcm: pcm.to_vec(),
+            rate,
+        });
+    }
+
+    pub fn clear_ai(&mut self) {
+        let at = self.started.elapsed();
+        self.push(Item::ClearAi { at });
+    }
+
+    pub fn transcript(&mut self, value: Value) {
+        self.line(TRANSCRIPTS, value);
+    }
+
+    pub fn event(&mut self, value: Value) {
+        self.line(EVENTS, value);
+    }
+
+    fn line(&mut self, artifact: usize, value: Value) {
+        let t_ms = self.started.elapsed().as_millis() as u64;
+        self.push(Item::Line {
+            artifact,
+            t_ms,
+            value,
+        });
+    }
+
+    fn push(&mut self, item: Item) {
+        if self.tx.try_send(item).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Kuyruğu kapatır; kalan tamponlar yüklenir ve `manifest.json` yazılır.
+    pub async fn finish(self) -> Result<ExportSummary, String> {
+        drop(self.tx);
+        self.task
+            .await
+            .map_err(|e| format!("export task aborted: {}", e))
+    }
+}
+
+struct Artifact {
+    name: &'static str,
+    key: String,
+    content_type: &'static str,
+    /// Henüz yüklenmemiş veri
+    buffer: Vec<u8>,
+    upload: Option<Upload>,
+    /// Başlık hariç alınan veri; 0 ise nesne yazılmaz
+    payload: u64,
+    /// Yüklenmiş bayt
+    bytes: u64,
+    parts: u32,
+    error: Option<String>,
+    resampler: Option<(u32, LinearResampler)>,
+    /// Ses yapıtlarında hedef örnekleme hızı
+    audio_rate: Option<u32>,
+}
+
+impl Artifact {
+    /// İstemci sesini yapıtın örnekleme hızına dönüştürür.
+    fn resample(&mut self, pcm: &[u8], from: u32, to: u32) -> Vec<u8> {
+        if self.resampler.as_ref().map(|(rate, _)| *rate) != Some(from) {
+            self.resampler = Some((from, LinearResampler::new(from, to)));
+        }
+        let samples = bytes_to_samples(pcm);
+        let samples = match self.resampler.as_mut() {
+            Some((_, resampler)) => resampler.process(&samples),
+            None => samples,
+        };
+        samples_to_bytes(&samples)
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.payload += bytes.len() as u64;
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    fn append_line(&mut self, t_ms: u64, mut value: Value) {
+        if let Value::Object(map) = &mut value {
+            map.insert("t_ms".into(), t_ms.into());
+        }
+        let len = self.buffer.len();
+        if serde_json::to_writer(&mut self.buffer, &value).is_ok() {
+            self.buffer.push(b'\n');
+            self.payload += (self.buffer.len() - len) as u64;
+        }
+    }
+
+    /// Tamponu bir parça olarak yükler; ilk parçada çok parçalı yükleme başlatılır.
+    async fn upload_part(&mut self, store: &ObjectStore) {
+        if self.error.is_some() {
+            self.buffer.clear();
+            return;
+        }
+        if self.upload.is_none() {
+            match store.start_upload(&self.key, self.content_type).await {
+                Ok(upload) => self.upload = Some(upload),
+                Err(e) => return self.fail(store, e.to_string()).await,
+            }
+        }
+        let part = std::mem::take(&mut self.buffer);
+        let len = part.len() as u64;
+        let Some(upload) = self.upload.as_mut() else {
+            return;
+        };
+        match store.upload_part(&self.key, upload, part).await {
+            Ok(()) => {
+                self.bytes += len;
+                self.parts += 1;
+            }
+            Err(e) => self.fail(store, e.to_string()).await,
+        }
+    }
+
+    async fn complete(&mut self, store: &ObjectStore) {
+        if self.error.is_some() || (self.payload == 0 && self.upload.is_none()) {
+            return;
+        }
+        if self.upload.is_none() {
+            // Tek parçaya sığan yapıt doğrudan yazılır
+            let body = std::mem::take(&mut self.buffer);
+            let len = body.len() as u64;
+            return match store.put(&self.key, body, self.content_type).await {
+                Ok(()) => {
+                    self.bytes = len;
+                    self.parts = 1;
+                }
+                Err(e) => self.fail(store, e.to_string()).await,
+            };
+        }
+        if !self.buffer.is_empty() {
+            self.upload_part(store).await;
+        }
+        if let Some(upload) = self.upload.take() {
+            if let Err(e) = store.complete_upload(&self.key, upload).await {
+                self.error = Some(e.to_string());
+                error!(event = "EXPORT_ARTIFACT_FAIL", key = %self.key, error = %e, "Export upload could not be completed.");
+            }
+        }
+    }
+
+    /// Yapıtı başarısız işaretler ve yarım yüklemeyi iptal eder; diğer yapıtlar devam eder.
+    async fn fail(&mut self, store: &ObjectStore, message: String) {
+        error!(event = "EXPORT_ARTIFACT_FAIL", key = %self.key, error = %message, "Export artifact failed; further data is discarded.");
+        self.error = Some(message);
+        self.buffer = Vec::new();
+        if let Some(upload) = self.upload.take() {
+            if let Err(e) = store.abort_upload(&self.key, upload).await {
+                warn!(event = "EXPORT_ABORT_FAIL", key = %self.key, error = %e, "Incomplete export upload could not be aborted.");
+            }
+        }
+    }
+
+    fn summary(&self, store: &ObjectStore) -> ArtifactSummary {
+        let status = if self.error.is_some() {
+            ArtifactStatus::Failed
+        } else if self.payload == 0 {
+            ArtifactStatus::Empty
+        } else {
+            ArtifactStatus::Completed
+        };
+        let audio = self.audio_rate.map(|rate| {
+            let samples = self.payload / 2;
+            AudioFormat {
+                encoding: "pcm_s16le",
+                sample_rate: rate,
+                channels: 1,
+                samples,
+                duration_secs: samples as f64 / rate as f64,
+            }
+        });
+        ArtifactSummary {
+            name: self.name,
+            location: store.location(&self.key),
+            bytes: self.bytes,
+            parts: self.parts,
+            status,
+            audio,
+            error: self.error.clone(),
+        }
+    }
+}
+
+/// AI sesi istemciye gerçek zamandan hızlı gönderilir ve istemcide gerçek zamanlı çalınır.
+/// Çalınma anı gelmeyen ses `ClearBuffer` ile düşebileceği için burada bekletilir ve yapıta
+/// ancak çalınmış sayıldığında eklenir (kayıttaki AI kanalıyla aynı model).
+#[derive(Default)]
+struct Playout {
+    pending: VecDeque<u8>,
+    /// `pending` başındaki örneğin çalınmaya başladığı an (oturum başından)
+    head_at: Duration,
+}
+
+impl Playout {
+    /// `at` anına kadar çalınmış baytları kuyruktan çıkarır.
+    fn advance(&mut self, at: Duration, rate: u32) -> Vec<u8> {
+        if self.pending.is_empty() {
+            self.head_at = self.head_at.max(at);
+            return Vec::new();
+        }
+        let elapsed = at.saturating_sub(self.head_at);
+        let samples = (elapsed.as_secs_f64() * rate as f64) as usize;
+        let take = (samples * 2).min(self.pending.len());
+        let played: Vec<u8> = self.pending.drain(..take).collect();
+        self.head_at += Duration::from_secs_f64((take / 2) as f64 / rate as f64);
+        if self.pending.is_empty() {
+            // Boşluktan sonra gelen ses geldiği anda çalmaya başlar
+            self.head_at = self.head_at.max(at);
+        }
+        played
+    }
+
+    fn push(&mut self, at: Duration, rate: u32, bytes: Vec<u8>) -> Vec<u8> {
+        let played = self.advance(at, rate);
+        self.pending.extend(bytes);
+        played
+    }
+
+    fn clear(&mut self, at: Duration, rate: u32) -> Vec<u8> {
+        let played = self.advance(at, rate);
+        self.pending.clear();
+        self.head_at = at;
+        played
+    }
+
+    fn drain(&mut self) -> Vec<u8> {
+        self.pending.drain(..).collect()
+    }
+}
+
+struct Uploader {
+    store: ObjectStore,
+    artifacts: [Artifact; 4],
+    playout: Playout,
+    manifest_key: String,
+    part_size: usize,
+    sample_rate: u32,
+    dropped: Arc<AtomicU64>,
+    summary: ExportSummary,
+}
+
+impl Uploader {
+    async fn run(mut self, mut rx: mpsc::Receiver<Item>) -> ExportSummary {
+        while let Some(item) = rx.recv().await {
+            let index = match item {
+                Item::Audio {
+                    track: Track::User,
+                    pcm,
+                    rate,
+                    ..
+                } => {
+                    let caller = &mut self.artifacts[CALLER_AUDIO];
+                    let bytes = caller.resample(&pcm, rate, self.sample_rate);
+                    caller.append_bytes(&bytes);
+                    CALLER_AUDIO
+                }
+                Item::Audio {
+                    track: Track::Ai,
+                    at,
+                    pcm,
+                    rate,
+                } => {
+                    let agent = &mut self.artifacts[AGENT_AUDIO];
+                    let bytes = agent.resample(&pcm, rate, self.sample_rate);
+                    let played = self.playout.push(at, self.sample_rate, bytes);
+                    agent.append_bytes(&played);
+                    AGENT_AUDIO
+                }
+                Item::ClearAi { at } => {
+                    let agent = &mut self.artifacts[AGENT_AUDIO];
+                    let played = self.playout.clear(at, self.sample_rate);
+                    agent.append_bytes(&played);
+                    if let Some((_, resampler)) = agent.resampler.as_mut() {
+                        resampler.reset();
+                    }
+                    AGENT_AUDIO
+                }
+                Item::Line {
+                    artifact,
+                    t_ms,
+                    value,
+                } => {
+                    self.artifacts[artifact].append_line(t_ms, value);
+                    artifact
+                }
+            };
+            let artifact = &mut self.artifacts[index];
+            if artifact.buffer.len() >= self.part_size {
+                artifact.upload_part(&self.store).await;
+            }
+        }
+
+        // Oturum sonunda bekleyen AI sesi kayıttaki gibi sonuna eklenir
+        let rest = self.playout.drain();
+        self.artifacts[AGENT_AUDIO].append_bytes(&rest);
+        for artifact in &mut self.artifacts {
+            artifact.complete(&self.store).await;
+        }
+        let mut summary = self.summary;
+        summary.ended_at = chrono::Utc::now().to_rfc3339();
+        summary.dropped = self.dropped.load(Ordering::Relaxed);
+        summary.artifacts = self
+            .artifacts
+            .iter()
+            .map(|a| a.summary(&self.store))
+            .collect();
+        let manifest = serde_json::to_vec_pretty(&summary).unwrap_or_default();
+        if let Err(e) = self
+            .store
+            .put(&self.manifest_key, manifest, "application/json")
+            .await
+        {
+            error!(event = "EXPORT_MANIFEST_FAIL", key = %self.manifest_key, error = %e, "Export manifest could not be written.");
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 1000;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn playout_releases_audio_in_real_time() {
+        let mut playout = Playout::default();
+        // 100 ms ses tek seferde gelir
+        assert!(playout.push(ms(0), RATE, vec![0; 200]).is_empty());
+        assert_eq!(playout.advance(ms(40), RATE).len(), 80);
+        assert_eq!(playout.advance(ms(40), RATE).len(), 0);
+        assert_eq!(playout.advance(ms(500), RATE).len(), 120);
+        // Boşluktan sonra gelen ses geldiği andan itibaren çalar
+        assert!(playout.push(ms(1000), RATE, vec![0; 100]).is_empty());
+        assert_eq!(playout.advance(ms(1020), RATE).len(), 40);
+    }
+
+    #[test]
+    fn clear_drops_unplayed_audio() {
+        let mut playout = Playout::default();
+        playout.push(ms(0), RATE, vec![1; 200]);
+        let played = playout.clear(ms(30), RATE);
+        assert_eq!(played.len(), 60);
+        assert!(playout.drain().is_empty());
+        // Temizlemeden sonra gelen ses yeniden zamanlanır
+        playout.push(ms(50), RATE, vec![2; 20]);
+        assert_eq!(playout.advance(ms(55), RATE), vec![2; 10]);
+        assert_eq!(playout.drain(), vec![2; 10]);
+    }
+
+    #[test]
+    fn audio_summary_reports_real_length() {
+        let store = ObjectStore::Local(crate::storage::local::LocalStore::new("/tmp", "b"));
+        let mut artifact = Artifact {
+            name: "agent.pcm",
+            key: "k/agent.pcm".into(),
+            content_type: "application/octet-stream",
+            buffer: Vec::new(),
+            upload: None,
+            payload: 0,
+            bytes: 0,
+            parts: 0,
+            error: None,
+            resampler: None,
+            audio_rate: Some(16000),
+        };
+        artifact.append_bytes(&[0; 32000]);
+        let audio = artifact.summary(&store).audio.unwrap();
+        assert_eq!(audio.samples, 16000);
+        assert_eq!(audio.duration_secs, 1.0);
+        assert_eq!(audio.encoding, "pcm_s16le");
+    }
+}
@@ -0,0 +1,160 @@
+// [YENİ]: Gelen ses akış denetimi (parça doğrulama + gerçek zaman katsayısı sınırı)
+use crate::audio::AudioCodec;
+use crate::config::AudioConfig;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    ChunkTooLarge,
+    MalformedChunk,
+    ChunkTooLong,
+    RealtimeFactor,
+}
+
+impl Violation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ChunkTooLarge => "chunk_too_large",
+            Self::MalformedChunk => "malformed_chunk",
+            Self::ChunkTooLong => "chunk_too_long",
+            Self::RealtimeFactor => "realtime_factor_exceeded",
+        }
+    }
+}
+
+/// Oturum başına gelen ses bekçisi. Gerçek zaman sınırı bir jeton kovasıdır:
+/// kova `realtime_burst_ms` kadar ses tutar ve saniyede `max_realtime_factor` saniye dolar.
+pub struct InboundGuard {
+    max_chunk_bytes: usize,
+    max_chunk_secs: f64,
+    max_rtf: f64,
+    burst_secs: f64,
+    bucket_secs: f64,
+    last_refill: Instant,
+    pub violations: u32,
+    pub max_violations: u32,
+}
+
+impl InboundGuard {
+    pub fn new(cfg: &AudioConfig) -> Self {
+        let burst_secs = cfg.realtime_burst_ms as f64 / 1000.0;
+        Self {
+            max_chunk_bytes: cfg.max_chunk_bytes,
+            max_chunk_secs: cfg.max_chunk_ms as f64 / 1000.0,
+            max_rtf: cfg.max_realtime_factor,
+            burst_secs,
+            bucket_secs: burst_secs,
+            last_refill: Instant::now(),
+            violations: 0,
+            max_violations: cfg.max_violations,
+        }
+    }
+
+    /// Çözmeden önceki ham parça kontrolü.
+    pub fn check_raw(&self, len: usize, codec: AudioCodec) -> Result<(), Violation> {
+        if len > self.max_chunk_bytes {
+            return Err(Violation::ChunkTooLarge);
+        }
+        if codec == AudioCodec::Pcm16 && !len.is_multiple_of(2) {
+            return Err(Violation::MalformedChunk);
+        }
+        Ok(())
+    }
+
+    /// Çözülmüş parçanın süresini sınırlara göre kabul eder ya da reddeder.
+    pub fn admit(&mut self, chunk_secs: f64) -> Result<(), Violation> {
+        if chunk_secs > self.max_chunk_secs {
+            return Err(Violation::ChunkTooLong);
+        }
+        if self.max_rtf <= 0.0 {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let refill = now.duration_since(self.last_refill).as_secs_f64() * self.max_rtf;
+        self.last_refill = now;
+        self.bucket_secs = (self.bucket_secs + refill).min(self.burst_secs);
+        if chunk_secs > self.bucket_secs {
+            return Err(Violation::RealtimeFactor);
+        }
+        self.bucket_secs -= chunk_secs;
+        Ok(())
+    }
+
+    /// İhlali sayar; sınır aşıldıysa `true` (oturum kapatılmalı).
+    pub fn record(&mut self) -> bool {
+        self.violations += 1;
+        self.max_violations > 0 && self.violations > self.max_violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn guard() -> InboundGuard {
+        InboundGuard::new(&AudioConfig {
+            max_chunk_bytes: 1024,
+            max_chunk_ms: 500,
+            max_realtime_factor: 1.0,
+            realtime_burst_ms: 200,
+            max_violations: 2,
+            ..AudioConfig::default()
+        })
+    }
+
+    #[test]
+    fn bucket_starts_full_and_drains() {
+        let mut g = guard();
+        assert_eq!(g.admit(0.2), Ok(()));
+        assert_eq!(g.admit(0.02), Err(Violation::RealtimeFactor));
+    }
+
+    #[test]
+    fn bucket_refills_at_realtime_factor() {
+        let mut g = guard();
+        g.admit(0.2).unwrap();
+        g.last_refill -= Duration::from_millis(100);
+        assert_eq!(g.admit(0.09), Ok(()));
+        assert_eq!(g.admit(0.05), Err(Violation::RealtimeFactor));
+    }
+
+    #[test]
+    fn refill_is_capped_at_burst() {
+        let mut g = guard();
+        g.admit(0.2).unwrap();
+        g.last_refill -= Duration::from_secs(10);
+        assert_eq!(g.admit(0.2), Ok(()));
+        assert_eq!(g.admit(0.02), Err(Violation::RealtimeFactor));
+    }
+
+    #[test]
+    fn zero_factor_disables_rate_limit() {
+        let mut g = InboundGuard::new(&AudioConfig {
+            max_realtime_factor: 0.0,
+            max_chunk_ms: 500,
+            ..AudioConfig::default()
+        });
+        for _ in 0..100 {
+            assert_eq!(g.admit(0.4), Ok(()));
+        }
+        assert_eq!(g.admit(0.6), Err(Violation::ChunkTooLong));
+    }
+
+    #[test]
+    fn raw_checks_and_violation_budget() {
+        let mut g = guard();
+        assert_eq!(
+            g.check_raw(2048, AudioCodec::Pcm16),
+            Err(Violation::ChunkTooLarge)
+        );
+        assert_eq!(
+            g.check_raw(321, AudioCodec::Pcm16),
+            Err(Violation::MalformedChunk)
+        );
+        assert_eq!(g.check_raw(320, AudioCodec::Pcm16), Ok(()));
+        assert!(!g.record());
+        assert!(!g.record());
+        assert!(g.record());
+    }
+}
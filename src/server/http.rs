@@ -1,6 +1,45 @@
+use crate::app::AppState;
+use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+use std::sync::Arc;
 
 pub async fn healthz() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
+
+// [YENİ]: Readiness -> mTLS sertifikaları geçerli değilse yeni trafik alınmamalı.
+pub async fn readyz(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let certs = state.cert_status();
+    // [YENİ]: Aşırı yükte yük dengeleyici trafiği diğer örneklere kaydırsın
+    let admission = state
+        .admission
+        .state(&state.current_config().admission, &state.metrics);
+    let ready = certs.is_ready() && admission.accepting;
+    let code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        code,
+        Json(json!({
+            "ready": ready,
+            "tls": certs,
+            "admission": admission,
+        })),
+    )
+}
+
+// [YENİ]: Prometheus metin formatında süreç sayaçları
+pub async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4",
+        )],
+        state.metrics.render(),
+    )
+}
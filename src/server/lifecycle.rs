@@ -0,0 +1,154 @@
+// [YENİ]: Oturum yaşam döngüsü olayları için sonlandırma sebebi, istemci bilgisi ve oturum sayaçları
+use super::socket::OutboundStats;
+use axum::http::{header, HeaderMap};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::time::Instant;
+
+/// `call.ended` olayındaki sonlandırma sebebi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    /// İstemci Close çerçevesi gönderdi
+    ClientClosed,
+    /// Bağlantı Close çerçevesi olmadan koptu / yazma başarısız oldu
+    ClientDisconnected,
+    /// İstemci `end_session` kontrolü gönderdi
+    EndSessionRequested,
+    OrchestratorInitFailed,
+    PipelineError,
+    /// Pipeline çıkış kanalı hatasız kapandı
+    PipelineCompleted,
+    PongTimeout,
+    IdleTimeout,
+    MaxDurationExceeded,
+    /// Gelen ses sınırları tekrar tekrar aşıldı
+    PolicyViolation,
+    /// Günlük ses kotası oturum içinde doldu
+    QuotaExceeded,
+    /// Yönetim API'si ile sonlandırıldı
+    AdminTerminated,
+    /// Arayan onay vermedi
+    ConsentDeclined,
+    ConsentTimeout,
+    /// İstemci okumadığı için giden kuyruk sert sınıra ulaştı
+    SlowConsumer,
+}
+
+impl EndReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ClientClosed => "client_closed",
+            Self::ClientDisconnected => "client_disconnected",
+            Self::EndSessionRequested => "end_session_requested",
+            Self::OrchestratorInitFailed => "orchestrator_init_failed",
+            Self::PipelineError => "pipeline_error",
+            Self::PipelineCompleted => "pipeline_completed",
+            Self::PongTimeout => "pong_timeout",
+            Self::IdleTimeout => "idle_timeout",
+            Self::MaxDurationExceeded => "max_duration_exceeded",
+            Self::PolicyViolation => "policy_violation",
+            Self::QuotaExceeded => "quota_exceeded",
+            Self::AdminTerminated => "admin_terminated",
+            Self::ConsentDeclined => "consent_declined",
+            Self::ConsentTimeout => "consent_timeout",
+            Self::SlowConsumer => "slow_consumer",
+        }
+    }
+}
+
+/// Yükseltme isteğinden alınan istemci bilgileri.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientMeta {
+    pub remote_addr: String,
+    pub forwarded_for: Option<String>,
+    pub user_agent: Option<String>,
+    /// `?sdk_version=` veya `X-Sdk-Version` başlığı
+    pub sdk_version: Option<String>,
+}
+
+impl ClientMeta {
+    pub fn from_request(
+        remote: SocketAddr,
+        headers: &HeaderMap,
+        params: &HashMap<String, String>,
+    ) -> Self {
+        let header_value = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            remote_addr: remote.to_string(),
+            forwarded_for: header_value("x-forwarded-for"),
+            user_agent: header_value(header::USER_AGENT.as_str()),
+            sdk_version: params
+                .get("sdk_version")
+                .cloned()
+                .or_else(|| header_value("x-sdk-version")),
+        }
+    }
+}
+
+/// Oturum boyunca biriken sayaçlar (`stream.session.ended` özeti).
+pub struct SessionStats {
+    started_at: Instant,
+    pub audio_in_secs: f64,
+    pub audio_out_secs: f64,
+    pub text_messages_in: u64,
+    /// Kesinleşmiş transkriptlerin konuşmacıya (`sender`) göre sayısı
+    pub turns: BTreeMap<String, u64>,
+    pub pipeline_restarts: u32,
+    /// Reddedilen gelen ses parçaları
+    pub inbound_violations: u32,
+    /// Oturum sonunda giden kuyruktan alınır
+    pub outbound: Option<OutboundStats>,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            audio_in_secs: 0.0,
+            audio_out_secs: 0.0,
+            text_messages_in: 0,
+            turns: BTreeMap::new(),
+            pipeline_restarts: 0,
+            inbound_violations: 0,
+            outbound: None,
+        }
+    }
+}
+
+impl SessionStats {
+    /// PCM16 mono bayt sayısını verilen hızda saniyeye çevirir.
+    pub fn pcm_seconds(bytes: usize, sample_rate: u32) -> f64 {
+        if sample_rate == 0 {
+            return 0.0;
+        }
+        bytes as f64 / (sample_rate as f64 * 2.0)
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.started_at.elapsed().as_secs_f64()
+    }
+
+    pub fn summary(&self) -> Value {
+        json!({
+            "duration_secs": round_ms(self.duration_secs()),
+            "audio_in_secs": round_ms(self.audio_in_secs),
+            "audio_out_secs": round_ms(self.audio_out_secs),
+            "text_messages_in": self.text_messages_in,
+            "turns": self.turns,
+            "pipeline_restarts": self.pipeline_restarts,
+            "inbound_violations": self.inbound_violations,
+            "outbound": self.outbound,
+        })
+    }
+}
+
+fn round_ms(secs: f64) -> f64 {
+    (secs * 1000.0).round() / 1000.0
+}
@@ -1,2 +1,22 @@
+pub mod admin;
+pub mod admission;
+pub mod auth;
+pub mod captions;
+pub mod consent;
+pub mod control;
+pub mod export;
+pub mod flow_control;
 pub mod http;
+pub mod lifecycle;
+pub mod monitor;
+pub mod pipeline_error;
+pub mod profile;
+pub mod quota;
+pub mod recording;
+pub mod registry;
+pub mod session_error;
+pub mod socket;
+pub mod supervisor;
+pub mod tls_listener;
+pub mod transcript;
 pub mod ws_handler;
@@ -0,0 +1,228 @@
+// [YENİ]: Süpervizör dinleme (sessiz izleme). Oturum döngüsü olayları bloklamayan bir
+// broadcast kanalına aynalar; dinleyici yoksa hiçbir kopya yapılmaz, yavaş dinleyici
+// yalnızca kendi çerçevelerini kaybeder ve asıl oturumun gecikmesi etkilenmez.
+use super::admin;
+use super::supervisor;
+use crate::app::AppState;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::info;
+
+const LISTENERS: &str = "stream_gateway_monitor_listeners";
+
+/// İkili ses çerçevelerinin ilk baytı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Channel {
+    /// İstemcinin (arayanın) pipeline'a giden sesi
+    Caller = 1,
+    /// Pipeline'ın ürettiği TTS sesi
+    Agent = 2,
+}
+
+#[derive(Debug, Clone)]
+pub enum MonitorEvent {
+    /// PCM16 mono; çerçeve: `[kanal:u8][örnekleme hızı:u32 LE][PCM]`
+    Audio(Arc<[u8]>),
+    /// JSON olay (transkript, duygu değişimi, tampon temizleme)
+    Event(Arc<str>),
+}
+
+/// Oturum başına ayna ucu. Klonlanabilir; tüm klonlar aynı kanala yazar.
+#[derive(Clone)]
+pub struct SessionMonitor {
+    tx: broadcast::Sender<MonitorEvent>,
+}
+
+impl SessionMonitor {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self { tx }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.tx.receiver_count() > 0
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<MonitorEvent> {
+        self.tx.subscribe()
+    }
+
+    pub fn audio(&self, channel: Channel, pcm: &[u8], sample_rate: u32) {
+        if !self.is_active() || pcm.is_empty() {
+            return;
+        }
+        let mut frame = Vec::with_capacity(pcm.len() + 5);
+        frame.push(channel as u8);
+        frame.extend_from_slice(&sample_rate.to_le_bytes());
+        frame.extend_from_slice(pcm);
+        let _ = self.tx.send(MonitorEvent::Audio(frame.into()));
+    }
+
+    /// `build` yalnızca dinleyici varsa çağrılır.
+    pub fn event(&self, build: impl FnOnce() -> Value) {
+        if self.is_active() {
+            let _ = self
+                .tx
+                .send(MonitorEvent::Event(build().to_string().into()));
+        }
+    }
+}
+
+/// `GET /admin/sessions/{session_id}/listen` — yalnızca okunur ayna akışı.
+/// Tarayıcılar WebSocket'te başlık gönderemediği için belirteç `?token=` ile de verilebilir.
+pub async fn listen(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    let token = admin::bearer(&headers).or_else(|| params.get("token").map(String::as_str));
+    let supervisor = match admin::authenticate(&state, token) {
+        Ok(principal) => principal,
+        Err(denied) => return denied.into_response(),
+    };
+    let Some(entry) = state.sessions.get(&session_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "session not found" })),
+        )
+            .into_response();
+    };
+    let rx = entry.monitor.subscribe();
+    let info = entry.info.clone();
+    ws.on_upgrade(move |socket| async move {
+        info!(event = "MONITOR_ATTACHED", trace_id = %info.trace_id, session_id = %session_id, supervisor = %supervisor, "Supervisor started listening.");
+        supervisor::publish_audit(&state, &info, &supervisor, "listen_start", json!({})).await;
+        state.metrics.add_gauge(LISTENERS, &[], 1.0);
+        stream_to_supervisor(socket, rx, &session_id, &info.trace_id).await;
+        state.metrics.add_gauge(LISTENERS, &[], -1.0);
+        supervisor::publish_audit(&state, &info, &supervisor, "listen_stop", json!({})).await;
+        info!(event = "MONITOR_DETACHED", trace_id = %info.trace_id, session_id = %session_id, supervisor = %supervisor, "Supervisor stopped listening.");
+    })
+}
+
+async fn stream_to_supervisor(
+    mut socket: WebSocket,
+    mut rx: broadcast::Receiver<MonitorEvent>,
+    session_id: &str,
+    trace_id: &str,
+) {
+    let started = json!({
+        "type": "MONITOR_STARTED",
+        "session_id": session_id,
+        "trace_id": trace_id,
+        "audio": "pcm16le",
+        "channels": { "caller": Channel::Caller as u8, "agent": Channel::Agent as u8 },
+    });
+    if socket
+        .send(Message::Text(started.to_string()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(message) = to_message(event) else {
+                    let ended = json!({ "type": "MONITOR_ENDED" }).to_string();
+                    let _ = socket.send(Message::Text(ended)).await;
+                    let _ = socket.send(Message::Close(None)).await;
+                    return;
+                };
+                if socket.send(message).await.is_err() {
+                    return;
+                }
+            }
+            // Süpervizörden gelen çerçeveler yok sayılır (salt okunur); yalnızca kapanış izlenir
+            incoming = socket.recv() => {
+                if let Some(Ok(Message::Close(_))) | Some(Err(_)) | None = incoming {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Kanal olayının dinleyiciye giden çerçevesi; oturum bittiyse (`Closed`) `None`.
+/// Geride kalan dinleyici kaybettiği olay sayısını `MONITOR_LAGGED` ile öğrenir.
+fn to_message(event: Result<MonitorEvent, RecvError>) -> Option<Message> {
+    match event {
+        Ok(MonitorEvent::Audio(frame)) => Some(Message::Binary(frame.to_vec())),
+        Ok(MonitorEvent::Event(text)) => Some(Message::Text(text.to_string())),
+        Err(RecvError::Lagged(skipped)) => Some(Message::Text(
+            json!({ "type": "MONITOR_LAGGED", "skipped": skipped }).to_string(),
+        )),
+        Err(RecvError::Closed) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(message: Option<Message>) -> Value {
+        match message {
+            Some(Message::Text(text)) => serde_json::from_str(&text).unwrap(),
+            other => panic!("expected a text frame, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn nothing_is_built_without_listeners() {
+        let monitor = SessionMonitor::new(4);
+        assert!(!monitor.is_active());
+        monitor.event(|| panic!("built without a listener"));
+        let rx = monitor.subscribe();
+        assert!(monitor.is_active());
+        drop(rx);
+        assert!(!monitor.is_active());
+    }
+
+    #[tokio::test]
+    async fn audio_frames_carry_channel_and_rate() {
+        let monitor = SessionMonitor::new(4);
+        let mut rx = monitor.subscribe();
+        monitor.audio(Channel::Agent, &[], 16000);
+        monitor.audio(Channel::Agent, &[1, 2], 16000);
+        match to_message(rx.recv().await) {
+            Some(Message::Binary(frame)) => {
+                assert_eq!(frame[0], Channel::Agent as u8);
+                assert_eq!(&frame[1..5], &16000u32.to_le_bytes());
+                assert_eq!(&frame[5..], &[1, 2]);
+            }
+            other => panic!("expected a binary frame, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn lagging_listener_is_told_how_much_it_missed() {
+        let monitor = SessionMonitor::new(2);
+        let mut slow = monitor.subscribe();
+        let mut fast = monitor.subscribe();
+        for n in 0..5 {
+            monitor.event(|| json!({ "type": "TRANSCRIPT", "n": n }));
+            // Hızlı dinleyici geride kalmaz
+            assert_eq!(text(to_message(fast.recv().await))["n"], n);
+        }
+
+        let notice = text(to_message(slow.recv().await));
+        assert_eq!(notice["type"], "MONITOR_LAGGED");
+        assert_eq!(notice["skipped"], 3);
+        // Bildirimden sonra kalan en yeni olaylar sırayla gelir
+        assert_eq!(text(to_message(slow.recv().await))["n"], 3);
+        assert_eq!(text(to_message(slow.recv().await))["n"], 4);
+
+        drop(monitor);
+        assert!(to_message(slow.recv().await).is_none());
+    }
+}
@@ -0,0 +1,178 @@
+// [YENİ]: Pipeline hatalarının istemciye bildirilecek kategoriye ve yeniden denenebilirliğe çevrilmesi
+use serde::Serialize;
+use std::error::Error;
+use std::io;
+use tonic::Code;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCategory {
+    /// Üst servise ulaşılamadı (bağlantı reddi/kopması, `UNAVAILABLE`)
+    UpstreamUnavailable,
+    /// Üst servis süresinde yanıt vermedi (`DEADLINE_EXCEEDED`)
+    UpstreamTimeout,
+    /// Üst servis yük nedeniyle reddetti (`RESOURCE_EXHAUSTED`, `ABORTED`)
+    UpstreamBusy,
+    /// Üst servis isteği başka bir gRPC koduyla reddetti
+    UpstreamRejected,
+    Internal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelineFailure {
+    pub category: FailureCategory,
+    pub retryable: bool,
+    /// Hata zincirinde bulunan gRPC durum kodu
+    #[serde(skip)]
+    pub grpc_code: Option<Code>,
+    /// Ham SDK hatası yalnızca loglanır; istemciye iç adresler sızmasın.
+    #[serde(skip)]
+    pub message: String,
+}
+
+/// SDK hatasının kaynak zinciri gezilerek `tonic::Status` kodu, gRPC taşıma hatası veya
+/// bağlantı kaynaklı `io::Error` aranır. Hiçbiri yoksa hata iç hata sayılır ve yeniden denenmez.
+pub fn classify(err: &(dyn Error + 'static)) -> PipelineFailure {
+    let failure = |category, retryable, grpc_code| PipelineFailure {
+        category,
+        retryable,
+        grpc_code,
+        message: err.to_string(),
+    };
+    let mut status_code = None;
+    let mut current = Some(err);
+    while let Some(e) = current {
+        if let Some(status) = e.downcast_ref::<tonic::Status>() {
+            let code = status.code();
+            match code {
+                Code::Unavailable => {
+                    return failure(FailureCategory::UpstreamUnavailable, true, Some(code))
+                }
+                Code::DeadlineExceeded => {
+                    return failure(FailureCategory::UpstreamTimeout, true, Some(code))
+                }
+                Code::ResourceExhausted | Code::Aborted => {
+                    return failure(FailureCategory::UpstreamBusy, true, Some(code))
+                }
+                // Bilinmeyen kod çoğunlukla taşıma hatasını sarar; zincirde aramaya devam edilir
+                Code::Unknown => status_code = Some(code),
+                _ => return failure(FailureCategory::UpstreamRejected, false, Some(code)),
+            }
+        } else if e.downcast_ref::<tonic::transport::Error>().is_some() {
+            return failure(FailureCategory::UpstreamUnavailable, true, status_code);
+        } else if let Some(io_err) = e.downcast_ref::<io::Error>() {
+            match io_err.kind() {
+                io::ErrorKind::TimedOut => {
+                    return failure(FailureCategory::UpstreamTimeout, true, status_code)
+                }
+                io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::NotConnected
+                | io::ErrorKind::BrokenPipe
+                | io::ErrorKind::UnexpectedEof => {
+                    return failure(FailureCategory::UpstreamUnavailable, true, status_code)
+                }
+                _ => {}
+            }
+        }
+        current = e.source();
+    }
+    match status_code {
+        Some(code) => failure(FailureCategory::UpstreamRejected, false, Some(code)),
+        None => failure(FailureCategory::Internal, false, None),
+    }
+}
+
+/// Pipeline görevi panikle ya da iptalle bittiğinde.
+pub fn aborted(reason: &str) -> PipelineFailure {
+    PipelineFailure {
+        category: FailureCategory::Internal,
+        retryable: false,
+        grpc_code: None,
+        message: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+
+    /// SDK'nın kendi hata tipini taklit eder: mesajı ne olursa olsun kaynağı taşır.
+    #[derive(Debug)]
+    struct Wrapped(Box<dyn Error + Send + Sync>);
+
+    impl fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("stt stream failed")
+        }
+    }
+
+    impl Error for Wrapped {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(self.0.as_ref())
+        }
+    }
+
+    fn classify_boxed(err: Box<dyn Error + Send + Sync>) -> PipelineFailure {
+        classify(&*err)
+    }
+
+    #[test]
+    fn status_codes_decide_retryability() {
+        let cases = [
+            (
+                Code::Unavailable,
+                FailureCategory::UpstreamUnavailable,
+                true,
+            ),
+            (
+                Code::DeadlineExceeded,
+                FailureCategory::UpstreamTimeout,
+                true,
+            ),
+            (Code::ResourceExhausted, FailureCategory::UpstreamBusy, true),
+            (
+                Code::InvalidArgument,
+                FailureCategory::UpstreamRejected,
+                false,
+            ),
+            (Code::Unknown, FailureCategory::UpstreamRejected, false),
+        ];
+        for (code, category, retryable) in cases {
+            let failure = classify_boxed(Box::new(tonic::Status::new(code, "connect timeout")));
+            assert_eq!(failure.category, category, "{:?}", code);
+            assert_eq!(failure.retryable, retryable, "{:?}", code);
+            assert_eq!(failure.grpc_code, Some(code));
+        }
+    }
+
+    #[test]
+    fn status_is_found_through_source_chain() {
+        let inner = tonic::Status::unavailable("dns error");
+        let failure = classify_boxed(Box::new(Wrapped(Box::new(inner))));
+        assert_eq!(failure.category, FailureCategory::UpstreamUnavailable);
+        assert!(failure.retryable);
+        assert_eq!(failure.message, "stt stream failed");
+    }
+
+    #[test]
+    fn connection_io_errors_are_retryable() {
+        let refused = io::Error::new(io::ErrorKind::ConnectionRefused, "refused");
+        let failure = classify_boxed(Box::new(Wrapped(Box::new(refused))));
+        assert_eq!(failure.category, FailureCategory::UpstreamUnavailable);
+        assert!(failure.retryable);
+
+        let other = io::Error::new(io::ErrorKind::InvalidData, "connection timeout");
+        assert!(!classify_boxed(Box::new(other)).retryable);
+    }
+
+    #[test]
+    fn untyped_errors_are_not_guessed_from_text() {
+        let failure = classify_boxed("tts unavailable: connection refused".into());
+        assert_eq!(failure.category, FailureCategory::Internal);
+        assert!(!failure.retryable);
+        assert_eq!(failure.grpc_code, None);
+    }
+}
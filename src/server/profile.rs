@@ -0,0 +1,294 @@
+// [YENİ]: Oturum Varsayılan Profilleri ve İstemci Değer Doğrulaması
+use crate::config::{AppConfig, SessionProfile};
+use sentiric_contracts::sentiric::stream::v1::SessionConfig;
+use serde::Serialize;
+
+/// Katmanlar birleştirildikten sonra oturumun kullandığı kesin değerler.
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveSession {
+    pub profile: String,
+    pub language: String,
+    pub system_prompt_id: String,
+    pub voice_id: String,
+    pub sample_rate: u32,
+    pub edge_mode: bool,
+    pub listen_only_mode: bool,
+    pub speak_only_mode: bool,
+    pub chat_only_mode: bool,
+    pub video_duration_seconds: u32,
+    pub video_aspect_ratio: String,
+    pub video_fps: u32,
+}
+
+impl EffectiveSession {
+    /// Pipeline'ı etkileyen alanlardan hangilerinin değiştiği (oturum içi yeniden yapılandırma için).
+    pub fn changed_fields(&self, next: &EffectiveSession) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        if self.language != next.language {
+            changed.push("language");
+        }
+        if self.system_prompt_id != next.system_prompt_id {
+            changed.push("system_prompt_id");
+        }
+        if self.voice_id != next.voice_id {
+            changed.push("tts_voice_id");
+        }
+        if self.sample_rate != next.sample_rate {
+            changed.push("sample_rate");
+        }
+        if self.edge_mode != next.edge_mode {
+            changed.push("edge_mode");
+        }
+        if self.listen_only_mode != next.listen_only_mode {
+            changed.push("listen_only_mode");
+        }
+        if self.speak_only_mode != next.speak_only_mode {
+            changed.push("speak_only_mode");
+        }
+        if self.chat_only_mode != next.chat_only_mode {
+            changed.push("chat_only_mode");
+        }
+        changed
+    }
+}
+
+/// Oturum ortasında gelen `Config` mesajını aktif istemci konfigürasyonu üzerine uygular.
+/// Boş metin alanları ve 0 örnekleme hızı "değiştirme" anlamına gelir; kimlik alanları korunur.
+pub fn merge_update(active: &SessionConfig, update: &SessionConfig) -> SessionConfig {
+    // proto3 bool alanları her mesajda tam durum olarak gelir; olduğu gibi alınır.
+    let mut merged = update.clone();
+    merged.session_id = active.session_id.clone();
+    merged.trace_id = active.trace_id.clone();
+    if merged.language.is_empty() {
+        merged.language = active.language.clone();
+    }
+    if merged.system_prompt_id.is_empty() {
+        merged.system_prompt_id = active.system_prompt_id.clone();
+    }
+    if merged.tts_voice_id.is_empty() {
+        merged.tts_voice_id = active.tts_voice_id.clone();
+    }
+    if merged.sample_rate == 0 {
+        merged.sample_rate = active.sample_rate;
+    }
+    merged
+}
+
+pub fn resolve(
+    app_cfg: &AppConfig,
+    profile_name: Option<&str>,
+    sess_cfg: &SessionConfig,
+) -> Result<EffectiveSession, String> {
+    let profile = resolve_profile(app_cfg, profile_name)?;
+
+    let pick = |client: &str, fallback: &Option<String>| -> String {
+        if client.is_empty() {
+            fallback.clone().unwrap_or_default()
+        } else {
+            client.to_string()
+        }
+    };
+    let language = pick(&sess_cfg.language, &profile.language);
+    let system_prompt_id = pick(&sess_cfg.system_prompt_id, &profile.system_prompt_id);
+    let voice_id = pick(&sess_cfg.tts_voice_id, &profile.voice_id);
+
+    check_allowed("language", &language, &profile.allowed_languages)?;
+    check_allowed(
+        "system_prompt_id",
+        &system_prompt_id,
+        &profile.allowed_prompts,
+    )?;
+    check_allowed("tts_voice_id", &voice_id, &profile.allowed_voices)?;
+
+    let sample_rate = if sess_cfg.sample_rate > 0 {
+        if !crate::config::SUPPORTED_SAMPLE_RATES.contains(&sess_cfg.sample_rate) {
+            return Err(format!(
+                "sample_rate {} is not supported (expected one of {:?}).",
+                sess_cfg.sample_rate,
+                crate::config::SUPPORTED_SAMPLE_RATES
+            ));
+        }
+        sess_cfg.sample_rate
+    } else {
+        profile.sample_rate.unwrap_or(16000)
+    };
+
+    Ok(EffectiveSession {
+        profile: profile_name.unwrap_or("default").to_string(),
+        language,
+        system_prompt_id,
+        voice_id,
+        sample_rate,
+        // proto3 bool alanlarında "belirtilmedi" ayrımı yok; istemci yalnızca açabilir.
+        edge_mode: sess_cfg.edge_mode || profile.edge_mode.unwrap_or(false),
+        listen_only_mode: sess_cfg.listen_only_mode || profile.listen_only_mode.unwrap_or(false),
+        speak_only_mode: sess_cfg.speak_only_mode || profile.speak_only_mode.unwrap_or(false),
+        chat_only_mode: sess_cfg.chat_only_mode || profile.chat_only_mode.unwrap_or(false),
+        video_duration_seconds: profile.video_duration_seconds.unwrap_or(5),
+        video_aspect_ratio: profile
+            .video_aspect_ratio
+            .clone()
+            .unwrap_or_else(|| "16:9".to_string()),
+        video_fps: profile.video_fps.unwrap_or(24),
+    })
+}
+
+fn resolve_profile(
+    app_cfg: &AppConfig,
+    profile_name: Option<&str>,
+) -> Result<SessionProfile, String> {
+    let tenant_defaults = SessionProfile::builtin().overlay(&app_cfg.session.defaults);
+    match profile_name.map(str::trim).filter(|n| !n.is_empty()) {
+        None => Ok(tenant_defaults),
+        Some(name) => app_cfg
+            .session
+            .profiles
+            .get(name)
+            .map(|p| tenant_defaults.overlay(p))
+            .ok_or_else(|| format!("profile '{}' is not defined.", name)),
+    }
+}
+
+fn check_allowed(field: &str, value: &str, allowed: &Option<Vec<String>>) -> Result<(), String> {
+    match allowed {
+        Some(list) if !list.is_empty() && !list.iter().any(|a| a == value) => Err(format!(
+            "{} '{}' is not allowed for this tenant/profile.",
+            field, value
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[&str]) -> Option<Vec<String>> {
+        Some(values.iter().map(|v| v.to_string()).collect())
+    }
+
+    fn app_config() -> AppConfig {
+        let mut cfg = AppConfig::default();
+        cfg.session.defaults = SessionProfile {
+            language: Some("tr-TR".into()),
+            voice_id: Some("omnivoice:male".into()),
+            allowed_languages: list(&["tr-TR", "en-US"]),
+            ..SessionProfile::default()
+        };
+        cfg.session.profiles.insert(
+            "support".into(),
+            SessionProfile {
+                language: Some("en-US".into()),
+                sample_rate: Some(8000),
+                edge_mode: Some(true),
+                ..SessionProfile::default()
+            },
+        );
+        cfg
+    }
+
+    #[test]
+    fn layers_tenant_then_profile_then_client() {
+        let cfg = app_config();
+        let tenant = resolve(&cfg, None, &SessionConfig::default()).unwrap();
+        assert_eq!(tenant.profile, "default");
+        assert_eq!(tenant.language, "tr-TR");
+        assert_eq!(tenant.voice_id, "omnivoice:male");
+        assert_eq!(tenant.system_prompt_id, "PROMPT_SYSTEM_DEFAULT");
+        assert_eq!(tenant.sample_rate, 16000);
+        assert!(!tenant.edge_mode);
+
+        let profile = resolve(&cfg, Some("support"), &SessionConfig::default()).unwrap();
+        assert_eq!(profile.profile, "support");
+        assert_eq!(profile.language, "en-US");
+        assert_eq!(profile.voice_id, "omnivoice:male");
+        assert_eq!(profile.sample_rate, 8000);
+        assert!(profile.edge_mode);
+
+        let client = SessionConfig {
+            language: "tr-TR".into(),
+            tts_voice_id: "omnivoice:female".into(),
+            sample_rate: 24000,
+            chat_only_mode: true,
+            ..Default::default()
+        };
+        let session = resolve(&cfg, Some("support"), &client).unwrap();
+        assert_eq!(session.language, "tr-TR");
+        assert_eq!(session.voice_id, "omnivoice:female");
+        assert_eq!(session.sample_rate, 24000);
+        assert!(session.chat_only_mode && session.edge_mode);
+    }
+
+    #[test]
+    fn rejects_values_outside_the_allowlist_and_unknown_profiles() {
+        let cfg = app_config();
+        let client = SessionConfig {
+            language: "de-DE".into(),
+            ..Default::default()
+        };
+        let err = resolve(&cfg, None, &client).unwrap_err();
+        assert!(err.contains("language 'de-DE' is not allowed"), "{}", err);
+
+        let client = SessionConfig {
+            sample_rate: 11025,
+            ..Default::default()
+        };
+        assert!(resolve(&cfg, None, &client)
+            .unwrap_err()
+            .contains("sample_rate 11025"));
+
+        let err = resolve(&cfg, Some("sales"), &SessionConfig::default()).unwrap_err();
+        assert_eq!(err, "profile 'sales' is not defined.");
+        // Boş profil adı tenant varsayılanı demektir
+        assert!(resolve(&cfg, Some(" "), &SessionConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn check_allowed_treats_missing_or_empty_lists_as_unrestricted() {
+        assert!(check_allowed("language", "de-DE", &None).is_ok());
+        assert!(check_allowed("language", "de-DE", &Some(Vec::new())).is_ok());
+        assert!(check_allowed("language", "tr-TR", &list(&["tr-TR"])).is_ok());
+        assert!(check_allowed("language", "", &list(&["tr-TR"])).is_err());
+    }
+
+    #[test]
+    fn restricted_allowlist_requires_a_default() {
+        let mut cfg = app_config();
+        cfg.session.defaults.language = None;
+        let err = cfg.validate().unwrap_err();
+        assert!(
+            err.contains("session.defaults.language must be set to an allowed value"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn merge_update_keeps_identity_and_unset_fields() {
+        let active = SessionConfig {
+            session_id: "s1".into(),
+            trace_id: "t1".into(),
+            language: "tr-TR".into(),
+            system_prompt_id: "P1".into(),
+            tts_voice_id: "v1".into(),
+            sample_rate: 16000,
+            edge_mode: true,
+            ..Default::default()
+        };
+        let update = SessionConfig {
+            session_id: "hijack".into(),
+            trace_id: "other".into(),
+            tts_voice_id: "v2".into(),
+            ..Default::default()
+        };
+        let merged = merge_update(&active, &update);
+        assert_eq!(merged.session_id, "s1");
+        assert_eq!(merged.trace_id, "t1");
+        assert_eq!(merged.language, "tr-TR");
+        assert_eq!(merged.system_prompt_id, "P1");
+        assert_eq!(merged.tts_voice_id, "v2");
+        assert_eq!(merged.sample_rate, 16000);
+        // Bool alanlar her mesajda tam durum taşır
+        assert!(!merged.edge_mode);
+    }
+}
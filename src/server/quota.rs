@@ -0,0 +1,406 @@
+// [YENİ]: Tenant / kullanıcı / istemci IP başına eşzamanlı oturum, dakikalık yeni oturum
+// ve günlük ses dakikası kotaları. Sayaçlar süreç içidir (her gateway örneği kendi payını tutar).
+use crate::config::{LimitsConfig, QuotaLimits};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+const SECS_PER_DAY: u64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    Tenant,
+    User,
+    Ip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Limit {
+    ConcurrentSessions,
+    SessionsPerMinute,
+    AudioMinutesPerDay,
+}
+
+impl Scope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tenant => "tenant",
+            Self::User => "user",
+            Self::Ip => "ip",
+        }
+    }
+
+    fn limits(self, cfg: &LimitsConfig) -> &QuotaLimits {
+        match self {
+            Self::Tenant => &cfg.tenant,
+            Self::User => &cfg.user,
+            Self::Ip => &cfg.ip,
+        }
+    }
+}
+
+impl Limit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ConcurrentSessions => "concurrent_sessions",
+            Self::SessionsPerMinute => "sessions_per_minute",
+            Self::AudioMinutesPerDay => "audio_minutes_per_day",
+        }
+    }
+}
+
+/// Aşılan kota; yükseltmede HTTP 429 gövdesi, oturum içinde `QUOTA_EXCEEDED` durum mesajı olur.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaDenied {
+    pub scope: Scope,
+    pub limit: Limit,
+    pub max: u32,
+    /// Eşzamanlı oturum sınırında tahmin edilemediği için boş
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Oturumun kota anahtarları (kapsam, değer).
+pub type QuotaKeys = Vec<(Scope, String)>;
+
+#[derive(Default)]
+struct Usage {
+    active: u32,
+    recent_starts: VecDeque<Instant>,
+    /// `audio_secs` değerinin ait olduğu UTC gün numarası
+    day: u64,
+    audio_secs: f64,
+}
+
+impl Usage {
+    fn roll(&mut self, now: Instant, today: u64) {
+        while self
+            .recent_starts
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW)
+        {
+            self.recent_starts.pop_front();
+        }
+        if self.day != today {
+            self.day = today;
+            self.audio_secs = 0.0;
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.active == 0 && self.recent_starts.is_empty() && self.audio_secs == 0.0
+    }
+}
+
+#[derive(Default)]
+pub struct QuotaRegistry {
+    usage: Mutex<HashMap<(Scope, String), Usage>>,
+}
+
+impl QuotaRegistry {
+    fn lock(&self) -> MutexGuard<'_, HashMap<(Scope, String), Usage>> {
+        self.usage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Tüm kapsamlar izin veriyorsa oturumu sayar; izin bırakılınca eşzamanlı sayaç düşer.
+    pub fn try_acquire(
+        self: &Arc<Self>,
+        keys: QuotaKeys,
+        cfg: &LimitsConfig,
+    ) -> Result<SessionPermit, QuotaDenied> {
+        self.try_acquire_at(keys, cfg, Instant::now(), utc_day())
+    }
+
+    fn try_acquire_at(
+        self: &Arc<Self>,
+        keys: QuotaKeys,
+        cfg: &LimitsConfig,
+        now: Instant,
+        today: u64,
+    ) -> Result<SessionPermit, QuotaDenied> {
+        let mut usage = self.lock();
+        usage.retain(|_, u| {
+            u.roll(now, today);
+            !u.is_idle()
+        });
+
+        for (scope, key) in &keys {
+            let Some(u) = usage.get(&(*scope, key.clone())) else {
+                continue;
+            };
+            let limits = scope.limits(cfg);
+            if limits.max_concurrent_sessions > 0 && u.active >= limits.max_concurrent_sessions {
+                return Err(QuotaDenied {
+                    scope: *scope,
+                    limit: Limit::ConcurrentSessions,
+                    max: limits.max_concurrent_sessions,
+                    retry_after_secs: None,
+                });
+            }
+            if limits.sessions_per_minute > 0
+                && u.recent_starts.len() >= limits.sessions_per_minute as usize
+            {
+                let oldest = u.recent_starts.front().copied().unwrap_or(now);
+                let wait = RATE_WINDOW.saturating_sub(now.duration_since(oldest));
+                return Err(QuotaDenied {
+                    scope: *scope,
+                    limit: Limit::SessionsPerMinute,
+                    max: limits.sessions_per_minute,
+                    retry_after_secs: Some(wait.as_secs().max(1)),
+                });
+            }
+            if let Some(denied) = audio_exhausted(*scope, u, limits) {
+                return Err(denied);
+            }
+        }
+
+        for key in &keys {
+            let u = usage.entry(key.clone()).or_default();
+            u.day = today;
+            u.active += 1;
+            u.recent_starts.push_back(now);
+        }
+        Ok(SessionPermit {
+            registry: self.clone(),
+            keys,
+        })
+    }
+
+    /// Pipeline'a iletilen sesi günlük kotaya ekler; kota dolduysa aşılan kapsamı döner.
+    pub fn record_audio(
+        &self,
+        keys: &QuotaKeys,
+        secs: f64,
+        cfg: &LimitsConfig,
+    ) -> Option<QuotaDenied> {
+        self.record_audio_at(keys, secs, cfg, Instant::now(), utc_day())
+    }
+
+    fn record_audio_at(
+        &self,
+        keys: &QuotaKeys,
+        secs: f64,
+        cfg: &LimitsConfig,
+        now: Instant,
+        today: u64,
+    ) -> Option<QuotaDenied> {
+        let mut usage = self.lock();
+        let mut denied = None;
+        for key in keys {
+            let u = usage.entry(key.clone()).or_default();
+            u.roll(now, today);
+            u.audio_secs += secs;
+            if denied.is_none() {
+                denied = audio_exhausted(key.0, u, key.0.limits(cfg));
+            }
+        }
+        denied
+    }
+}
+
+fn audio_exhausted(scope: Scope, usage: &Usage, limits: &QuotaLimits) -> Option<QuotaDenied> {
+    let max = limits.audio_minutes_per_day;
+    (max > 0 && usage.audio_secs >= max as f64 * 60.0).then(|| QuotaDenied {
+        scope,
+        limit: Limit::AudioMinutesPerDay,
+        max,
+        retry_after_secs: Some(secs_until_utc_midnight()),
+    })
+}
+
+/// Oturum boyunca tutulur; düşürüldüğünde eşzamanlı oturum sayaçları azalır.
+pub struct SessionPermit {
+    registry: Arc<QuotaRegistry>,
+    keys: QuotaKeys,
+}
+
+impl SessionPermit {
+    pub fn keys(&self) -> &QuotaKeys {
+        &self.keys
+    }
+}
+
+impl Drop for SessionPermit {
+    fn drop(&mut self) {
+        let mut usage = self.registry.lock();
+        for key in &self.keys {
+            if let Some(u) = usage.get_mut(key) {
+                u.active = u.active.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// IP kotasının anahtarı. `X-Forwarded-For` girdilerini sağdan her güvenilir proxy bir tane ekler;
+/// istemci, sağdan `trusted_hops`'uncu adrestir. Soldaki girdiler istemcinin kendi yazdıklarıdır ve
+/// kullanılmaz. 0 = başlık yok sayılır, bağlantı adresi kullanılır. Zincir `trusted_hops`'tan
+/// kısaysa istek proxy'lerin hepsinden geçmemiştir ve girdiler istemciye aittir; bağlantı adresi
+/// kullanılır.
+pub fn client_ip(remote: IpAddr, forwarded_for: Option<&str>, trusted_hops: usize) -> String {
+    if trusted_hops == 0 {
+        return remote.to_string();
+    }
+    let trusted: Vec<&str> = forwarded_for
+        .into_iter()
+        .flat_map(|v| v.rsplit(','))
+        .map(str::trim)
+        .take(trusted_hops)
+        .collect();
+    match trusted.last() {
+        Some(ip) if trusted.len() == trusted_hops && trusted.iter().all(|ip| !ip.is_empty()) => {
+            ip.to_string()
+        }
+        _ => remote.to_string(),
+    }
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn utc_day() -> u64 {
+    unix_secs() / SECS_PER_DAY
+}
+
+fn secs_until_utc_midnight() -> u64 {
+    SECS_PER_DAY - unix_secs() % SECS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(scope: QuotaLimits) -> LimitsConfig {
+        LimitsConfig {
+            user: scope,
+            ..LimitsConfig::default()
+        }
+    }
+
+    fn keys(user: &str) -> QuotaKeys {
+        vec![(Scope::User, user.to_string())]
+    }
+
+    #[test]
+    fn concurrent_limit_is_released_when_permit_drops() {
+        let registry = Arc::new(QuotaRegistry::default());
+        let cfg = limits(QuotaLimits {
+            max_concurrent_sessions: 1,
+            ..QuotaLimits::default()
+        });
+        let permit = registry.try_acquire(keys("u1"), &cfg).unwrap();
+        let denied = registry.try_acquire(keys("u1"), &cfg).err().unwrap();
+        assert_eq!(denied.limit, Limit::ConcurrentSessions);
+        assert_eq!(denied.scope, Scope::User);
+        assert!(registry.try_acquire(keys("u2"), &cfg).is_ok());
+        drop(permit);
+        assert!(registry.try_acquire(keys("u1"), &cfg).is_ok());
+    }
+
+    #[test]
+    fn rate_window_slides_after_a_minute() {
+        let registry = Arc::new(QuotaRegistry::default());
+        let cfg = limits(QuotaLimits {
+            sessions_per_minute: 2,
+            ..QuotaLimits::default()
+        });
+        let start = Instant::now();
+        let today = utc_day();
+        for _ in 0..2 {
+            registry
+                .try_acquire_at(keys("u1"), &cfg, start, today)
+                .unwrap();
+        }
+        let later = start + Duration::from_secs(20);
+        let denied = registry
+            .try_acquire_at(keys("u1"), &cfg, later, today)
+            .err()
+            .unwrap();
+        assert_eq!(denied.limit, Limit::SessionsPerMinute);
+        assert_eq!(denied.retry_after_secs, Some(40));
+        assert!(registry
+            .try_acquire_at(keys("u1"), &cfg, start + RATE_WINDOW, today)
+            .is_ok());
+    }
+
+    #[test]
+    fn audio_quota_resets_on_the_next_utc_day() {
+        let registry = Arc::new(QuotaRegistry::default());
+        let cfg = limits(QuotaLimits {
+            audio_minutes_per_day: 1,
+            ..QuotaLimits::default()
+        });
+        let now = Instant::now();
+        let today = utc_day();
+        let permit = registry
+            .try_acquire_at(keys("u1"), &cfg, now, today)
+            .unwrap();
+        assert!(registry
+            .record_audio_at(permit.keys(), 59.0, &cfg, now, today)
+            .is_none());
+        let denied = registry
+            .record_audio_at(permit.keys(), 1.0, &cfg, now, today)
+            .unwrap();
+        assert_eq!(denied.limit, Limit::AudioMinutesPerDay);
+        drop(permit);
+        assert!(registry
+            .try_acquire_at(keys("u1"), &cfg, now, today)
+            .is_err());
+
+        let permit = registry
+            .try_acquire_at(keys("u1"), &cfg, now, today + 1)
+            .unwrap();
+        assert!(registry
+            .record_audio_at(permit.keys(), 30.0, &cfg, now, today + 1)
+            .is_none());
+    }
+
+    #[test]
+    fn idle_usage_is_pruned() {
+        let registry = Arc::new(QuotaRegistry::default());
+        let cfg = LimitsConfig::default();
+        let now = Instant::now();
+        let today = utc_day();
+        drop(
+            registry
+                .try_acquire_at(keys("u1"), &cfg, now, today)
+                .unwrap(),
+        );
+        let _permit = registry
+            .try_acquire_at(keys("u2"), &cfg, now + RATE_WINDOW, today)
+            .unwrap();
+        let usage = registry.lock();
+        assert_eq!(usage.len(), 1);
+        assert!(usage.contains_key(&(Scope::User, "u2".to_string())));
+    }
+
+    #[test]
+    fn client_ip_uses_trusted_hops_from_the_right() {
+        let remote: IpAddr = "10.0.0.1".parse().unwrap();
+        let xff = Some("6.6.6.6, 203.0.113.7, 10.0.0.2");
+        assert_eq!(client_ip(remote, xff, 0), "10.0.0.1");
+        assert_eq!(client_ip(remote, xff, 1), "10.0.0.2");
+        assert_eq!(client_ip(remote, xff, 2), "203.0.113.7");
+        assert_eq!(client_ip(remote, None, 1), "10.0.0.1");
+        assert_eq!(client_ip(remote, Some(""), 1), "10.0.0.1");
+    }
+
+    #[test]
+    fn short_forwarded_chain_falls_back_to_the_connection() {
+        let remote: IpAddr = "10.0.0.1".parse().unwrap();
+        // Proxy'leri atlayan istemci başlığı kendisi yazmıştır; soldaki girdiye güvenilmez
+        assert_eq!(client_ip(remote, Some("6.6.6.6"), 2), "10.0.0.1");
+        assert_eq!(client_ip(remote, Some("6.6.6.6, 10.0.0.2"), 3), "10.0.0.1");
+        assert_eq!(
+            client_ip(remote, Some("6.6.6.6, , 10.0.0.2"), 2),
+            "10.0.0.1"
+        );
+    }
+}
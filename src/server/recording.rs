@@ -0,0 +1,387 @@
+// [YENİ]: Uyum (compliance) kaydı. Kullanıcı ve AI sesi zaman hizalı stereo WAV'a
+// (sol: kullanıcı, sağ: AI), transkript/duygu/kontrol olayları JSONL yan dosyasına yazılır.
+// Disk işlemleri ayrı bir bloklayan görevde yapılır; oturum döngüsü yalnızca kuyruğa ekler.
+use crate::audio::resample::{bytes_to_samples, LinearResampler};
+use crate::config::RecordingConfig;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+const WAV_HEADER_LEN: u64 = 44;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Track {
+    /// Sol kanal: istemcinin pipeline'a giden sesi
+    User,
+    /// Sağ kanal: pipeline'ın TTS sesi
+    Ai,
+}
+
+enum Record {
+    Audio {
+        track: Track,
+        at: Duration,
+        pcm: Vec<u8>,
+        rate: u32,
+    },
+    /// İstemci tamponu temizlendi; henüz çalınmamış AI sesi kayda girmez
+    ClearAi {
+        at: Duration,
+    },
+    Event {
+        at: Duration,
+        event: Value,
+    },
+}
+
+/// `recording.completed` olayının gövdesi.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingSummary {
+    pub wav_path: String,
+    pub sidecar_path: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub duration_secs: f64,
+    pub wav_bytes: u64,
+    pub events: u64,
+    /// Yazıcı kuyruğu dolduğu için atılan kayıt öğeleri
+    pub dropped: u64,
+}
+
+/// Oturum döngüsü tarafı. Tüm çağrılar bloklamaz.
+pub struct Recorder {
+    tx: mpsc::Sender<Record>,
+    started: Instant,
+    dropped: u64,
+    task: JoinHandle<Result<RecordingSummary, String>>,
+}
+
+impl Recorder {
+    pub fn start(cfg: &RecordingConfig, session_id: &str) -> Result<Self, String> {
+        let now = chrono::Utc::now();
+        let dir = Path::new(&cfg.directory).join(now.format("%Y-%m-%d").to_string());
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("recording directory '{}': {}", dir.display(), e))?;
+        let stem = format!("{}-{}", sanitize(session_id), now.format("%Y%m%dT%H%M%S"));
+        let wav_path = dir.join(format!("{}.wav", stem));
+        let sidecar_path = dir.join(format!("{}.jsonl", stem));
+        let writer = TrackWriter::create(wav_path, sidecar_path, cfg.sample_rate)?;
+
+        let (tx, rx) = mpsc::channel(cfg.queue_capacity.max(1));
+        let task = tokio::task::spawn_blocking(move || writer.run(rx));
+        Ok(Self {
+            tx,
+            started: Instant::now(),
+            dropped: 0,
+            task,
+        })
+    }
+
+    pub fn audio(&mut self, track: Track, pcm: &[u8], rate: u32) {
+        if pcm.is_empty() {
+            return;
+        }
+        let record = Record::Audio {
+            track,
+            at: self.started.elapsed(),
+            pcm: pcm.to_vec(),
+            rate,
+        };
+        self.push(record);
+    }
+
+    pub fn clear_ai(&mut self) {
+        let at = self.started.elapsed();
+        self.push(Record::ClearAi { at });
+    }
+
+    pub fn event(&mut self, event: Value) {
+        let at = self.started.elapsed();
+        self.push(Record::Event { at, event });
+    }
+
+    fn push(&mut self, record: Record) {
+        if self.tx.try_send(record).is_err() {
+            self.dropped += 1;
+        }
+    }
+
+    /// Kuyruğu kapatır, kalan sesi yazar ve WAV başlığını tamamlar.
+    pub async fn finish(self) -> Result<RecordingSummary, String> {
+        drop(self.tx);
+        let mut summary = self
+            .task
+            .await
+            .map_err(|e| format!("recording writer aborted: {}", e))??;
+        summary.dropped = self.dropped;
+        Ok(summary)
+    }
+}
+
+#[derive(Default)]
+struct TrackBuffer {
+    /// Yazılmış son çerçeveden itibaren bekleyen örnekler
+    pending: VecDeque<i16>,
+    resampler: Option<(u32, LinearResampler)>,
+}
+
+impl TrackBuffer {
+    fn resample(&mut self, pcm: &[u8], from: u32, to: u32) -> Vec<i16> {
+        if self.resampler.as_ref().map(|(rate, _)| *rate) != Some(from) {
+            self.resampler = Some((from, LinearResampler::new(from, to)));
+        }
+        let samples = bytes_to_samples(pcm);
+        match self.resampler.as_mut() {
+            Some((_, resampler)) => resampler.process(&samples),
+            None => samples,
+        }
+    }
+}
+
+struct TrackWriter {
+    wav: BufWriter<File>,
+    sidecar: BufWriter<File>,
+    wav_path: PathBuf,
+    sidecar_path: PathBuf,
+    rate: u32,
+    /// Dosyaya yazılmış stereo çerçeve sayısı
+    written: u64,
+    user: TrackBuffer,
+    ai: TrackBuffer,
+    events: u64,
+}
+
+impl TrackWriter {
+    fn create(wav_path: PathBuf, sidecar_path: PathBuf, rate: u32) -> Result<Self, String> {
+        let open = |path: &Path| {
+            File::create(path)
+                .map(BufWriter::new)
+                .map_err(|e| format!("recording file '{}': {}", path.display(), e))
+        };
+        let mut wav = open(&wav_path)?;
+        write_wav_header(&mut wav, 2, rate, 0).map_err(|e| e.to_string())?;
+        Ok(Self {
+            sidecar: open(&sidecar_path)?,
+            wav,
+            wav_path,
+            sidecar_path,
+            rate,
+            written: 0,
+            user: TrackBuffer::default(),
+            ai: TrackBuffer::default(),
+            events: 0,
+        })
+    }
+
+    fn run(mut self, mut rx: mpsc::Receiver<Record>) -> Result<RecordingSummary, String> {
+        while let Some(record) = rx.blocking_recv() {
+            self.apply(record).map_err(|e| e.to_string())?;
+        }
+        self.finish().map_err(|e| e.to_string())
+    }
+
+    fn position(&self, at: Duration) -> u64 {
+        (at.as_secs_f64() * self.rate as f64) as u64
+    }
+
+    fn apply(&mut self, record: Record) -> std::io::Result<()> {
+        match record {
+            Record::Audio {
+                track,
+                at,
+                pcm,
+                rate,
+            } => {
+                let now = self.position(at);
+                self.flush_until(now)?;
+                let target = self.rate;
+                let buffer = match track {
+                    Track::User => &mut self.user,
+                    Track::Ai => &mut self.ai,
+                };
+                // `flush_until` sonrası kanal en erken şimdiki andan başlar (boşluk sessizlikle
+                // dolmuştur). AI sesi gerçek zamandan hızlı üretildiği için bekleyenlerin sonuna
+                // eklenir; böylece istemcideki çalma sırası korunur.
+                let samples = buffer.resample(&pcm, rate, target);
+                buffer.pending.extend(samples);
+            }
+            Record::ClearAi { at } => {
+                let now = self.position(at);
+                self.flush_until(now)?;
+                self.ai.pending.clear();
+                if let Some((_, resampler)) = self.ai.resampler.as_mut() {
+                    resampler.reset();
+                }
+            }
+            Record::Event { at, mut event } => {
+                if let Value::Object(map) = &mut event {
+                    map.insert("t_ms".into(), (at.as_millis() as u64).into());
+                }
+                serde_json::to_writer(&mut self.sidecar, &event)?;
+                self.sidecar.write_all(b"\n")?;
+                self.events += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// `until` çerçevesine kadar iki kanalı da yazar; verisi olmayan kanal sessizlikle doldurulur.
+    fn flush_until(&mut self, until: u64) -> std::io::Result<()> {
+        while self.written < until {
+            let left = self.user.pending.pop_front().unwrap_or(0);
+            let right = self.ai.pending.pop_front().unwrap_or(0);
+            self.wav.write_all(&left.to_le_bytes())?;
+            self.wav.write_all(&right.to_le_bytes())?;
+            self.written += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> std::io::Result<RecordingSummary> {
+        let remaining = self.user.pending.len().max(self.ai.pending.len()) as u64;
+        self.flush_until(self.written + remaining)?;
+        let data_len = self.written * 4;
+        self.wav.seek(SeekFrom::Start(0))?;
+        write_wav_header(&mut self.wav, 2, self.rate, data_len)?;
+        self.wav.flush()?;
+        self.sidecar.flush()?;
+        Ok(RecordingSummary {
+            wav_path: self.wav_path.display().to_string(),
+            sidecar_path: self.sidecar_path.display().to_string(),
+            sample_rate: self.rate,
+            channels: 2,
+            duration_secs: self.written as f64 / self.rate as f64,
+            wav_bytes: WAV_HEADER_LEN + data_len,
+            events: self.events,
+            dropped: 0,
+        })
+    }
+}
+
+/// 16-bit PCM RIFF başlığı; `data_len` kayıt sonunda güncellenir. RIFF'in 4 GiB sınırını aşan
+/// uzunluk sınırda tutulur (oynatıcılar dosya sonuna kadar okur).
+fn write_wav_header(
+    out: &mut impl Write,
+    channels: u16,
+    rate: u32,
+    data_len: u64,
+) -> std::io::Result<()> {
+    let bits: u16 = 16;
+    let block_align = channels * bits / 8;
+    let data_len = data_len.min(u32::MAX as u64 - WAV_HEADER_LEN) as u32;
+    out.write_all(b"RIFF")?;
+    out.write_all(&(36 + data_len).to_le_bytes())?;
+    out.write_all(b"WAVEfmt ")?;
+    out.write_all(&16u32.to_le_bytes())?;
+    out.write_all(&1u16.to_le_bytes())?;
+    out.write_all(&channels.to_le_bytes())?;
+    out.write_all(&rate.to_le_bytes())?;
+    out.write_all(&(rate * block_align as u32).to_le_bytes())?;
+    out.write_all(&block_align.to_le_bytes())?;
+    out.write_all(&bits.to_le_bytes())?;
+    out.write_all(b"data")?;
+    out.write_all(&data_len.to_le_bytes())
+}
+
+/// İstemcinin verdiği session_id dosya/nesne adında kullanılmadan önce temizlenir.
+pub fn sanitize(id: &str) -> String {
+    let cleaned: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect();
+    if cleaned.is_empty() {
+        "session".to_string()
+    } else {
+        cleaned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(buf: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([buf[at], buf[at + 1]])
+    }
+
+    fn u32_at(buf: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn wav_header_layout() {
+        let mut header = Vec::new();
+        write_wav_header(&mut header, 2, 16000, 6400).unwrap();
+        assert_eq!(header.len() as u64, WAV_HEADER_LEN);
+        assert_eq!(&header[0..4], b"RIFF");
+        assert_eq!(u32_at(&header, 4), 36 + 6400);
+        assert_eq!(&header[8..16], b"WAVEfmt ");
+        assert_eq!(u32_at(&header, 16), 16);
+        assert_eq!(u16_at(&header, 20), 1);
+        assert_eq!(u16_at(&header, 22), 2);
+        assert_eq!(u32_at(&header, 24), 16000);
+        assert_eq!(u32_at(&header, 28), 16000 * 4);
+        assert_eq!(u16_at(&header, 32), 4);
+        assert_eq!(u16_at(&header, 34), 16);
+        assert_eq!(&header[36..40], b"data");
+        assert_eq!(u32_at(&header, 40), 6400);
+    }
+
+    #[test]
+    fn wav_header_clamps_unknown_length() {
+        let mut header = Vec::new();
+        write_wav_header(&mut header, 1, 8000, u64::MAX).unwrap();
+        let data_len = u32_at(&header, 40);
+        assert_eq!(data_len as u64, u32::MAX as u64 - WAV_HEADER_LEN);
+        assert_eq!(u32_at(&header, 4), data_len + 36);
+        assert_eq!(u32_at(&header, 28), 8000 * 2);
+    }
+
+    #[test]
+    fn sanitize_keeps_ids_safe_for_paths() {
+        assert_eq!(sanitize("call-42_A"), "call-42_A");
+        assert_eq!(sanitize("../../etc/passwd"), "______etc_passwd");
+        assert_eq!(sanitize("a b\\c\0d"), "a_b_c_d");
+        assert_eq!(sanitize("çağrı"), "_a_r_");
+        assert_eq!(sanitize(""), "session");
+        assert_eq!(sanitize(&"x".repeat(200)).len(), 64);
+    }
+
+    #[tokio::test]
+    async fn finished_recording_has_consistent_header() {
+        let dir = std::env::temp_dir().join(format!("recording-test-{}", std::process::id()));
+        let cfg = RecordingConfig {
+            enabled: true,
+            directory: dir.display().to_string(),
+            sample_rate: 16000,
+            queue_capacity: 16,
+        };
+        let mut recorder = Recorder::start(&cfg, "../call").unwrap();
+        recorder.audio(Track::User, &[1u8; 1600], 16000);
+        recorder.audio(Track::Ai, &[2u8; 3200], 16000);
+        recorder.event(serde_json::json!({ "type": "TRANSCRIPT" }));
+        let summary = recorder.finish().await.unwrap();
+
+        let wav = fs::read(&summary.wav_path).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(summary.wav_path.contains("___call-"));
+        assert_eq!(wav.len() as u64, summary.wav_bytes);
+        assert_eq!(u32_at(&wav, 40) as u64, summary.wav_bytes - WAV_HEADER_LEN);
+        // AI kanalı en az kendi 1600 örneği kadar yazılır
+        assert!(summary.duration_secs >= 0.1);
+        assert_eq!(summary.events, 1);
+    }
+}
@@ -0,0 +1,271 @@
+// [YENİ]: Bu gateway örneğinde çalışan oturumların kaydı. Değişken oturum durumu oturum
+// döngüsünün sahipliğindedir; yönetim istekleri döngüye komut kanalı üzerinden iletilir.
+use super::auth::Access;
+use super::captions::CaptionFeed;
+use super::lifecycle::ClientMeta;
+use super::monitor::SessionMonitor;
+use super::socket::Outbound;
+use super::supervisor::SupervisorCommand;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::sync::{mpsc, oneshot};
+
+/// Oturum döngüsüne dışarıdan gelen komutlar.
+pub enum SessionCommand {
+    /// Döngünün anlık durumunu (efektif konfigürasyon, pipeline durumu, sayaçlar) ister
+    Inspect(oneshot::Sender<Value>),
+    /// Oturumu `admin_terminated` sebebiyle kapatır
+    Terminate,
+    /// Süpervizör fısıltısı / araya girme / devralma
+    Supervisor(SupervisorCommand),
+}
+
+/// Oturum boyunca değişmeyen kimlik bilgileri.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub trace_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub profile: Option<String>,
+    pub client: ClientMeta,
+}
+
+pub struct SessionEntry {
+    pub info: SessionInfo,
+    pub started_at: DateTime<Utc>,
+    /// İstemciden alınan ham çerçeve baytları
+    pub bytes_in: AtomicU64,
+    /// Süpervizör dinleme aynası
+    pub monitor: SessionMonitor,
+    /// Canlı altyazı akışı (kapalıysa `None`)
+    pub captions: Option<CaptionFeed>,
+    /// Bağlantıya verilen oturum erişim belirteci (altyazı ucu)
+    access_token: String,
+    outbound: Outbound,
+    commands: mpsc::Sender<SessionCommand>,
+}
+
+impl SessionEntry {
+    pub fn new(
+        info: SessionInfo,
+        outbound: Outbound,
+        monitor: SessionMonitor,
+        captions: Option<CaptionFeed>,
+        access_token: String,
+    ) -> (Self, mpsc::Receiver<SessionCommand>) {
+        let (commands, rx) = mpsc::channel(8);
+        let entry = Self {
+            info,
+            started_at: Utc::now(),
+            bytes_in: AtomicU64::new(0),
+            monitor,
+            captions,
+            access_token,
+            outbound,
+            commands,
+        };
+        (entry, rx)
+    }
+
+    /// Yönetim belirteci ya da bu bağlantıya verilen belirteç.
+    pub fn grants(&self, access: &Access) -> bool {
+        access.allows([&self.access_token])
+    }
+
+    pub fn add_bytes_in(&self, bytes: usize) {
+        self.bytes_in.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Döngüye sorulmadan verilebilen bilgiler (liste görünümü).
+    pub fn summary(&self) -> Value {
+        let mut summary = json!(self.info);
+        summary["started_at"] = json!(self.started_at.to_rfc3339());
+        summary["duration_secs"] = json!((Utc::now() - self.started_at).num_seconds());
+        summary["bytes_in"] = json!(self.bytes_in.load(Ordering::Relaxed));
+        summary["bytes_out"] = json!(self.outbound.stats().bytes_sent);
+        summary
+    }
+
+    /// Döngü meşgulse (ör. pipeline yeniden kuruluyor) ya da bitmişse `None`.
+    pub async fn inspect(&self, timeout: std::time::Duration) -> Option<Value> {
+        let (tx, rx) = oneshot::channel();
+        self.commands
+            .send_timeout(SessionCommand::Inspect(tx), timeout)
+            .await
+            .ok()?;
+        tokio::time::timeout(timeout, rx).await.ok()?.ok()
+    }
+
+    pub async fn send_timeout(
+        &self,
+        command: SessionCommand,
+        timeout: std::time::Duration,
+    ) -> Result<(), mpsc::error::SendTimeoutError<SessionCommand>> {
+        self.commands.send_timeout(command, timeout).await
+    }
+}
+
+#[derive(Default)]
+pub struct SessionRegistry {
+    sessions: RwLock<HashMap<String, Arc<SessionEntry>>>,
+}
+
+impl SessionRegistry {
+    /// Aynı session_id ile yeniden bağlanan istemci eski kaydın yerini alır.
+    pub fn register(self: &Arc<Self>, entry: SessionEntry) -> Registration {
+        let entry = Arc::new(entry);
+        self.sessions
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(entry.info.session_id.clone(), entry.clone());
+        Registration {
+            registry: self.clone(),
+            entry,
+        }
+    }
+
+    pub fn list(&self) -> Vec<Arc<SessionEntry>> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        sessions.sort_by_key(|s| s.started_at);
+        sessions
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<Arc<SessionEntry>> {
+        self.sessions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(session_id)
+            .cloned()
+    }
+}
+
+/// Oturum bitince (düşürülünce) kayıt silinir.
+pub struct Registration {
+    registry: Arc<SessionRegistry>,
+    entry: Arc<SessionEntry>,
+}
+
+impl Registration {
+    pub fn entry(&self) -> &SessionEntry {
+        &self.entry
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut sessions = self
+            .registry
+            .sessions
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        if sessions
+            .get(&self.entry.info.session_id)
+            .is_some_and(|current| Arc::ptr_eq(current, &self.entry))
+        {
+            sessions.remove(&self.entry.info.session_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::socket;
+
+    fn entry(session_id: &str) -> (SessionEntry, mpsc::Receiver<SessionCommand>) {
+        let info = SessionInfo {
+            session_id: session_id.into(),
+            trace_id: format!("trace-{}", session_id),
+            tenant_id: "default".into(),
+            user_id: "token-abc".into(),
+            profile: None,
+            client: ClientMeta {
+                remote_addr: "10.0.0.1:5000".into(),
+                forwarded_for: None,
+                user_agent: None,
+                sdk_version: None,
+            },
+        };
+        SessionEntry::new(
+            info,
+            socket::detached(4),
+            SessionMonitor::new(4),
+            None,
+            "session-token".into(),
+        )
+    }
+
+    #[test]
+    fn registration_lives_as_long_as_the_session() {
+        let registry = Arc::new(SessionRegistry::default());
+        let (first, _rx1) = entry("a");
+        let (second, _rx2) = entry("b");
+        let a = registry.register(first);
+        let b = registry.register(second);
+
+        let ids: Vec<_> = registry
+            .list()
+            .iter()
+            .map(|s| s.info.session_id.clone())
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(registry.get("a").unwrap().info.trace_id, "trace-a");
+
+        drop(a);
+        assert!(registry.get("a").is_none());
+        assert_eq!(registry.list().len(), 1);
+        drop(b);
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn reconnect_replaces_and_outlives_the_old_registration() {
+        let registry = Arc::new(SessionRegistry::default());
+        let (old, _rx1) = entry("a");
+        let (new, _rx2) = entry("a");
+        let old = registry.register(old);
+        let new = registry.register(new);
+        assert_eq!(registry.list().len(), 1);
+
+        // Eski bağlantının kapanması yeni kaydı silmez
+        drop(old);
+        let current = registry.get("a").expect("new registration kept");
+        assert!(std::ptr::eq(current.as_ref(), new.entry()));
+    }
+
+    #[test]
+    fn access_is_granted_to_admins_and_the_session_token() {
+        let (entry, _rx) = entry("a");
+        assert!(entry.grants(&Access::Admin("ops".into())));
+        assert!(entry.grants(&Access::Session("session-token".into())));
+        assert!(!entry.grants(&Access::Session("other-token".into())));
+    }
+
+    #[tokio::test]
+    async fn commands_reach_the_session_loop() {
+        let (entry, mut rx) = entry("a");
+        assert!(entry
+            .send_timeout(SessionCommand::Terminate, std::time::Duration::from_secs(1))
+            .await
+            .is_ok());
+        assert!(matches!(rx.recv().await, Some(SessionCommand::Terminate)));
+
+        // Döngü yanıt vermezse inspect beklemeyi bırakır
+        assert!(entry
+            .inspect(std::time::Duration::from_millis(20))
+            .await
+            .is_none());
+        assert!(matches!(rx.recv().await, Some(SessionCommand::Inspect(_))));
+    }
+}
@@ -0,0 +1,116 @@
+// [YENİ]: Oturumu sonlandıran protokol hataları -> yapılandırılmış yanıt + WebSocket kapanış kodu
+use super::quota::QuotaDenied;
+use super::socket::Outbound;
+use axum::extract::ws::CloseFrame;
+use sentiric_contracts::sentiric::stream::v1::stream_session_response::Data as RespData;
+use serde_json::json;
+
+/// Uygulamaya özel kapanış kodları (RFC 6455: 4000-4999 özel kullanım aralığı).
+#[derive(Debug, Clone)]
+pub enum SessionError {
+    HandshakeTimeout,
+    BadProtobuf(String),
+    WrongFirstMessage,
+    ConfigRejected(String),
+    OrchestratorInit,
+    IdleTimeout,
+    MaxDurationExceeded,
+    PipelineFailed,
+    PolicyViolation,
+    QuotaExceeded(QuotaDenied),
+    AdminTerminated,
+    ConsentDeclined,
+    ConsentTimeout,
+    SlowConsumer,
+}
+
+impl SessionError {
+    /// Sayaç etiketi ve istemciye giden hata kodu.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::HandshakeTimeout => "handshake_timeout",
+            Self::BadProtobuf(_) => "bad_protobuf",
+            Self::WrongFirstMessage => "wrong_first_message",
+            Self::ConfigRejected(_) => "config_rejected",
+            Self::OrchestratorInit => "orchestrator_init_failed",
+            Self::IdleTimeout => "idle_timeout",
+            Self::MaxDurationExceeded => "max_duration_exceeded",
+            Self::PipelineFailed => "pipeline_failed",
+            Self::PolicyViolation => "policy_violation",
+            Self::QuotaExceeded(_) => "quota_exceeded",
+            Self::AdminTerminated => "admin_terminated",
+            Self::ConsentDeclined => "consent_declined",
+            Self::ConsentTimeout => "consent_timeout",
+            Self::SlowConsumer => "slow_consumer",
+        }
+    }
+
+    pub fn close_code(&self) -> u16 {
+        match self {
+            Self::HandshakeTimeout => 4001,
+            Self::BadProtobuf(_) => 4002,
+            Self::WrongFirstMessage => 4003,
+            Self::ConfigRejected(_) => 4005,
+            Self::OrchestratorInit => 4006,
+            Self::IdleTimeout => 4007,
+            Self::MaxDurationExceeded => 4008,
+            Self::PipelineFailed => 4009,
+            Self::PolicyViolation => 4010,
+            Self::QuotaExceeded(_) => 4011,
+            Self::AdminTerminated => 4012,
+            Self::ConsentDeclined => 4013,
+            Self::ConsentTimeout => 4014,
+            Self::SlowConsumer => 4015,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::HandshakeTimeout => {
+                "no SessionConfig received within the handshake timeout.".into()
+            }
+            Self::BadProtobuf(e) => format!("frame is not a valid StreamSessionRequest: {}", e),
+            Self::WrongFirstMessage => "first message must be a binary SessionConfig frame.".into(),
+            Self::ConfigRejected(reason) => reason.clone(),
+            Self::OrchestratorInit => "AI pipeline could not be started.".into(),
+            Self::IdleTimeout => "no audio or text received within the idle timeout.".into(),
+            Self::MaxDurationExceeded => "maximum session duration reached.".into(),
+            Self::PipelineFailed => "AI pipeline stopped unexpectedly.".into(),
+            Self::PolicyViolation => "inbound audio limits were exceeded repeatedly.".into(),
+            Self::QuotaExceeded(denied) => format!(
+                "{} quota '{}' reached (limit {}).",
+                denied.scope.as_str(),
+                denied.limit.as_str(),
+                denied.max
+            ),
+            Self::AdminTerminated => "session terminated by an administrator.".into(),
+            Self::ConsentDeclined => "caller declined consent; nothing was processed.".into(),
+            Self::ConsentTimeout => "caller consent was not given in time.".into(),
+            Self::SlowConsumer => "client is not reading; outbound queue limit reached.".into(),
+        }
+    }
+
+    /// İstemci uyumluluğu için eski `CONFIG_REJECTED` tipi korunur.
+    fn status_type(&self) -> &'static str {
+        match self {
+            Self::ConfigRejected(_) => "CONFIG_REJECTED",
+            _ => "SESSION_ERROR",
+        }
+    }
+}
+
+/// Hata durum mesajını ve ardından ilgili kapanış kodlu Close çerçevesini kuyruğa alır.
+pub fn close_with_error(out: &Outbound, err: &SessionError) {
+    let status_json = json!({
+        "type": err.status_type(),
+        "code": err.code(),
+        "reason": err.message(),
+        "close_code": err.close_code(),
+    })
+    .to_string();
+    out.send(RespData::StatusUpdate(status_json));
+    out.close(Some(CloseFrame {
+        code: err.close_code(),
+        reason: err.code().into(),
+    }));
+}
@@ -0,0 +1,524 @@
+// [YENİ]: WebSocket okuma/yazma görevleri ve sınırlı, politikalı giden kuyruk.
+// Yavaş bir istemci artık gelen ses/bilişsel olay döngüsünü ve orkestratörü bloklamaz.
+use crate::metrics::Metrics;
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, StreamExt};
+use prost::Message as ProstMessage;
+use sentiric_contracts::sentiric::stream::v1::stream_session_response::Data as RespData;
+use sentiric_contracts::sentiric::stream::v1::StreamSessionResponse;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinHandle;
+
+pub const QUEUE_DEPTH: &str = "stream_gateway_outbound_queue_depth";
+const FRAMES_SENT: &str = "stream_gateway_ws_frames_sent_total";
+const SEND_SECONDS: &str = "stream_gateway_ws_send_seconds_total";
+const FRAMES_DROPPED: &str = "stream_gateway_outbound_dropped_total";
+
+/// Kuyrukta birden fazla bekletilmesine gerek olmayan, yalnızca son değeri önemli durum mesajları.
+const COALESCED_STATUS_TYPES: [&str; 3] = ["VAD", "MOOD_SHIFT", "FLOW_VIOLATION"];
+/// Oturum sonunda kalan kuyruğun gönderilmesi için beklenecek azami süre.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+/// Atılamayan kontrol/durum mesajları kapasiteyi en fazla bu kata kadar aşabilir; sonrası yavaş istemcidir.
+const HARD_CAP_FACTOR: usize = 2;
+
+pub type Inbound = mpsc::Receiver<Result<Message, axum::Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameKind {
+    Audio,
+    Status,
+    Other,
+    Close,
+}
+
+struct Frame {
+    kind: FrameKind,
+    status_type: Option<String>,
+    message: Message,
+    enqueued_at: Instant,
+}
+
+/// Oturum başına giden kuyruk ölçümleri.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OutboundStats {
+    pub frames_sent: u64,
+    pub bytes_sent: u64,
+    pub frames_dropped: u64,
+    pub max_queue_depth: usize,
+    /// Kuyruğa girişten sokete yazılana kadar geçen süre
+    pub send_latency_ms_avg: f64,
+    pub send_latency_ms_max: f64,
+    #[serde(skip)]
+    latency_ms_total: f64,
+}
+
+struct Queue {
+    frames: VecDeque<Frame>,
+    /// Yazıcı durdu ya da Close kuyruğa alındı; yeni çerçeve kabul edilmez
+    closed: bool,
+    /// Kuyruk sert sınıra ulaştı; oturum yavaş istemci olarak kapatılmalı
+    overrun: bool,
+    stats: OutboundStats,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    notify: Notify,
+    overrun: Notify,
+    capacity: usize,
+    metrics: Arc<Metrics>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Giden kuyruğa yazma ucu. `send` bloklamaz; bağlantı kapandıysa `false` döner.
+#[derive(Clone)]
+pub struct Outbound {
+    shared: Arc<Shared>,
+}
+
+/// Okuyucu/yazıcı görevleri. Oturum bitiminde `finish` ile toplanır.
+pub struct SocketTasks {
+    reader: JoinHandle<()>,
+    writer: JoinHandle<()>,
+}
+
+pub fn split(
+    socket: WebSocket,
+    inbound_capacity: usize,
+    outbound_capacity: usize,
+    metrics: Arc<Metrics>,
+) -> (Outbound, Inbound, SocketTasks) {
+    let (sink, stream) = socket.split();
+    let (inbound_tx, inbound_rx) = mpsc::channel(inbound_capacity);
+    let shared = new_shared(outbound_capacity, metrics);
+
+    let reader = tokio::spawn(run_reader(stream, inbound_tx));
+    let writer = tokio::spawn(run_writer(sink, shared.clone()));
+    (
+        Outbound { shared },
+        inbound_rx,
+        SocketTasks { reader, writer },
+    )
+}
+
+fn new_shared(capacity: usize, metrics: Arc<Metrics>) -> Arc<Shared> {
+    Arc::new(Shared {
+        queue: Mutex::new(Queue {
+            frames: VecDeque::with_capacity(capacity),
+            closed: false,
+            overrun: false,
+            stats: OutboundStats::default(),
+        }),
+        notify: Notify::new(),
+        overrun: Notify::new(),
+        capacity,
+        metrics,
+    })
+}
+
+impl Outbound {
+    pub fn send(&self, data: RespData) -> bool {
+        let (kind, status_type) = match &data {
+            RespData::AudioResponse(_) => (FrameKind::Audio, None),
+            RespData::StatusUpdate(json) => (FrameKind::Status, status_type_of(json)),
+            _ => (FrameKind::Other, None),
+        };
+        let clears_audio = matches!(data, RespData::ClearAudioBuffer(_));
+        let resp = StreamSessionResponse { data: Some(data) };
+        let mut buf = Vec::new();
+        if resp.encode(&mut buf).is_err() {
+            return true;
+        }
+
+        let mut queue = self.shared.lock();
+        if queue.closed {
+            return false;
+        }
+        // ClearBuffer: istemcinin çalmayacağı bayat TTS sesini hiç göndermeyiz
+        if clears_audio {
+            self.evict(
+                &mut queue,
+                "cleared",
+                |f| f.kind == FrameKind::Audio,
+                usize::MAX,
+            );
+        }
+        if let Some(t) = status_type.as_deref() {
+            if COALESCED_STATUS_TYPES.contains(&t) {
+                self.evict(
+                    &mut queue,
+                    "coalesced",
+                    |f| f.status_type.as_deref() == Some(t),
+                    usize::MAX,
+                );
+            }
+        }
+        if queue.frames.len() >= self.shared.capacity {
+            // Önce en eski ses, yoksa birleştirilebilir durum mesajı feda edilir
+            let evicted =
+                match self.evict(&mut queue, "overflow", |f| f.kind == FrameKind::Audio, 1) {
+                    0 => self.evict(
+                        &mut queue,
+                        "overflow",
+                        |f| {
+                            f.status_type
+                                .as_deref()
+                                .is_some_and(|t| COALESCED_STATUS_TYPES.contains(&t))
+                        },
+                        1,
+                    ),
+                    n => n,
+                };
+            // Kontrol/durum mesajları atılmaz; kapasite geçici olarak sert sınıra kadar aşılır
+            if evicted == 0 && kind == FrameKind::Audio {
+                queue.stats.frames_dropped += 1;
+                self.shared
+                    .metrics
+                    .incr(FRAMES_DROPPED, &[("reason", "overflow")]);
+                return true;
+            }
+            if evicted == 0 && queue.frames.len() >= self.shared.capacity * HARD_CAP_FACTOR {
+                // İstemci okumuyor: bekleyenler atılır, hata ve Close çerçevesine yer açılır
+                self.evict(&mut queue, "slow_consumer", |_| true, usize::MAX);
+                queue.stats.frames_dropped += 1;
+                self.shared
+                    .metrics
+                    .incr(FRAMES_DROPPED, &[("reason", "slow_consumer")]);
+                queue.overrun = true;
+                self.shared.overrun.notify_one();
+                return true;
+            }
+        }
+        self.push(&mut queue, kind, status_type, Message::Binary(buf));
+        true
+    }
+
+    /// Kuyruk doluysa ping kuyruğa alınmaz: istemci zaten okumuyordur ve yarı açık
+    /// bağlantıyı pong zaman aşımı yakalar. Bekleyen bir ping varsa ikincisi eklenmez.
+    pub fn ping(&self) -> bool {
+        let mut queue = self.shared.lock();
+        if queue.closed {
+            return false;
+        }
+        if queue.frames.len() >= self.shared.capacity {
+            queue.stats.frames_dropped += 1;
+            self.shared
+                .metrics
+                .incr(FRAMES_DROPPED, &[("reason", "overflow")]);
+            return true;
+        }
+        if queue
+            .frames
+            .iter()
+            .any(|f| matches!(f.message, Message::Ping(_)))
+        {
+            return true;
+        }
+        self.push(
+            &mut queue,
+            FrameKind::Other,
+            None,
+            Message::Ping(Vec::new()),
+        );
+        true
+    }
+
+    /// Close çerçevesini kuyruğun sonuna ekler; sonrasında gelen çerçeveler atılır.
+    pub fn close(&self, frame: Option<CloseFrame<'static>>) {
+        let mut queue = self.shared.lock();
+        if queue.closed {
+            return;
+        }
+        self.push(&mut queue, FrameKind::Close, None, Message::Close(frame));
+        queue.closed = true;
+    }
+
+    /// Kuyruk sert sınıra ulaşınca tamamlanır; oturum döngüsü bu noktada oturumu kapatır.
+    pub async fn overrun(&self) {
+        if self.shared.lock().overrun {
+            return;
+        }
+        // `notify_one` bekleyen yoksa izni saklar; kontrol ile bekleme arasındaki sinyal kaybolmaz
+        self.shared.overrun.notified().await;
+    }
+
+    pub fn stats(&self) -> OutboundStats {
+        self.shared.lock().stats.clone()
+    }
+
+    fn push(
+        &self,
+        queue: &mut Queue,
+        kind: FrameKind,
+        status_type: Option<String>,
+        message: Message,
+    ) {
+        queue.frames.push_back(Frame {
+            kind,
+            status_type,
+            message,
+            enqueued_at: Instant::now(),
+        });
+        queue.stats.max_queue_depth = queue.stats.max_queue_depth.max(queue.frames.len());
+        self.shared.metrics.add_gauge(QUEUE_DEPTH, &[], 1.0);
+        self.shared.notify.notify_one();
+    }
+
+    fn evict(
+        &self,
+        queue: &mut Queue,
+        reason: &str,
+        pred: impl Fn(&Frame) -> bool,
+        limit: usize,
+    ) -> usize {
+        let mut removed = 0;
+        queue.frames.retain(|f| {
+            if removed < limit && pred(f) {
+                removed += 1;
+                false
+            } else {
+                true
+            }
+        });
+        if removed > 0 {
+            queue.stats.frames_dropped += removed as u64;
+            self.shared
+                .metrics
+                .add(FRAMES_DROPPED, &[("reason", reason)], removed as f64);
+            self.shared
+                .metrics
+                .add_gauge(QUEUE_DEPTH, &[], -(removed as f64));
+        }
+        removed
+    }
+}
+
+impl SocketTasks {
+    /// Kuyrukta kalanları (Close dahil) kısa bir süre boyunca göndermeye çalışır, sonra görevleri durdurur.
+    pub async fn finish(self, outbound: &Outbound) {
+        outbound.close(None);
+        let mut writer = self.writer;
+        if tokio::time::timeout(FLUSH_TIMEOUT, &mut writer)
+            .await
+            .is_err()
+        {
+            writer.abort();
+        }
+        self.reader.abort();
+        // Yazıcı iptal edildiyse kuyrukta kalanları göstergeden düş
+        let mut queue = outbound.shared.lock();
+        let remaining = queue.frames.len();
+        queue.frames.clear();
+        if remaining > 0 {
+            outbound
+                .shared
+                .metrics
+                .add_gauge(QUEUE_DEPTH, &[], -(remaining as f64));
+        }
+    }
+}
+
+async fn run_reader(
+    mut stream: SplitStream<WebSocket>,
+    inbound_tx: mpsc::Sender<Result<Message, axum::Error>>,
+) {
+    while let Some(msg) = stream.next().await {
+        let failed = msg.is_err();
+        // Kanal doluysa burada beklenir; TCP geri basıncı istemciye yansır
+        if inbound_tx.send(msg).await.is_err() || failed {
+            break;
+        }
+    }
+}
+
+async fn run_writer(mut sink: SplitSink<WebSocket, Message>, shared: Arc<Shared>) {
+    loop {
+        let next = shared.lock().frames.pop_front();
+        let Some(frame) = next else {
+            if shared.lock().closed {
+                break;
+            }
+            shared.notify.notified().await;
+            continue;
+        };
+        shared.metrics.add_gauge(QUEUE_DEPTH, &[], -1.0);
+
+        let is_close = frame.kind == FrameKind::Close;
+        let bytes = match &frame.message {
+            Message::Binary(buf) => buf.len() as u64,
+            _ => 0,
+        };
+        let result = sink.send(frame.message).await;
+        let latency_ms = frame.enqueued_at.elapsed().as_secs_f64() * 1000.0;
+        if result.is_err() {
+            shared.lock().closed = true;
+            break;
+        }
+
+        shared.metrics.incr(FRAMES_SENT, &[]);
+        shared.metrics.add(SEND_SECONDS, &[], latency_ms / 1000.0);
+        {
+            let mut queue = shared.lock();
+            let stats = &mut queue.stats;
+            stats.frames_sent += 1;
+            stats.bytes_sent += bytes;
+            stats.latency_ms_total += latency_ms;
+            stats.send_latency_ms_max = stats.send_latency_ms_max.max(latency_ms);
+            stats.send_latency_ms_avg = stats.latency_ms_total / stats.frames_sent as f64;
+        }
+        if is_close {
+            break;
+        }
+    }
+    let _ = sink.close().await;
+}
+
+fn status_type_of(json: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(json)
+        .ok()?
+        .get("type")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Yazıcı görevi olmayan giden kuyruk; diğer modüllerin testleri içindir.
+#[cfg(test)]
+pub(crate) fn detached(capacity: usize) -> Outbound {
+    Outbound {
+        shared: new_shared(capacity, Arc::new(Metrics::default())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outbound(capacity: usize) -> Outbound {
+        detached(capacity)
+    }
+
+    fn status(kind: &str, n: u32) -> RespData {
+        RespData::StatusUpdate(serde_json::json!({ "type": kind, "n": n }).to_string())
+    }
+
+    fn kinds(out: &Outbound) -> Vec<(FrameKind, Option<String>)> {
+        out.shared
+            .lock()
+            .frames
+            .iter()
+            .map(|f| (f.kind, f.status_type.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn clear_buffer_evicts_queued_audio() {
+        let out = outbound(8);
+        out.send(RespData::AudioResponse(vec![1; 4]));
+        out.send(status("TRANSCRIPT", 1));
+        out.send(RespData::AudioResponse(vec![2; 4]));
+        out.send(RespData::ClearAudioBuffer(true));
+        assert_eq!(
+            kinds(&out),
+            vec![
+                (FrameKind::Status, Some("TRANSCRIPT".into())),
+                (FrameKind::Other, None)
+            ]
+        );
+        assert_eq!(out.stats().frames_dropped, 2);
+    }
+
+    #[test]
+    fn coalesced_status_keeps_only_latest() {
+        let out = outbound(8);
+        out.send(status("VAD", 1));
+        out.send(status("TRANSCRIPT", 1));
+        out.send(status("VAD", 2));
+        out.send(status("VAD", 3));
+        let queue = out.shared.lock();
+        let vad: Vec<_> = queue
+            .frames
+            .iter()
+            .filter(|f| f.status_type.as_deref() == Some("VAD"))
+            .collect();
+        assert_eq!(vad.len(), 1);
+        assert_eq!(queue.frames.len(), 2);
+        assert_eq!(
+            queue.frames.back().unwrap().status_type.as_deref(),
+            Some("VAD")
+        );
+        assert_eq!(queue.stats.frames_dropped, 2);
+    }
+
+    #[test]
+    fn overflow_evicts_oldest_audio_before_dropping_new_audio() {
+        let out = outbound(3);
+        out.send(RespData::AudioResponse(vec![1]));
+        out.send(status("TRANSCRIPT", 1));
+        out.send(status("TRANSCRIPT", 2));
+        // Dolu kuyrukta en eski ses yer açar
+        out.send(status("TRANSCRIPT", 3));
+        assert!(kinds(&out).iter().all(|(k, _)| *k == FrameKind::Status));
+        // Atılacak ses yoksa yeni ses atılır, kuyruk büyümez
+        assert!(out.send(RespData::AudioResponse(vec![2])));
+        assert_eq!(out.shared.lock().frames.len(), 3);
+        assert_eq!(out.stats().frames_dropped, 2);
+    }
+
+    #[tokio::test]
+    async fn control_frames_are_capped_and_signal_slow_consumer() {
+        let out = outbound(2);
+        for n in 0..4 {
+            out.send(status("TRANSCRIPT", n));
+        }
+        assert_eq!(out.shared.lock().frames.len(), 4);
+        assert!(!out.shared.lock().overrun);
+
+        out.send(status("TRANSCRIPT", 4));
+        {
+            let queue = out.shared.lock();
+            assert!(queue.overrun);
+            assert!(queue.frames.is_empty());
+            assert_eq!(queue.stats.frames_dropped, 5);
+        }
+        tokio::time::timeout(Duration::from_secs(1), out.overrun())
+            .await
+            .expect("overrun must be signalled");
+    }
+
+    #[test]
+    fn closed_queue_rejects_frames() {
+        let out = outbound(4);
+        out.close(None);
+        assert!(!out.send(status("TRANSCRIPT", 1)));
+        assert!(!out.ping());
+        assert_eq!(kinds(&out), vec![(FrameKind::Close, None)]);
+    }
+
+    #[test]
+    fn pings_stay_within_capacity() {
+        let out = outbound(2);
+        assert!(out.ping());
+        // Bekleyen ping varken yenisi eklenmez
+        assert!(out.ping());
+        assert_eq!(kinds(&out), vec![(FrameKind::Other, None)]);
+
+        out.send(status("TRANSCRIPT", 1));
+        // Dolu kuyrukta ping atılır, bağlantı açık sayılır
+        assert!(out.ping());
+        assert_eq!(out.shared.lock().frames.len(), 2);
+        assert_eq!(out.stats().frames_dropped, 1);
+
+        out.close(None);
+        assert!(!out.ping());
+    }
+}
@@ -0,0 +1,245 @@
+// [YENİ]: Süpervizör müdahaleleri (fısıltı, araya girme, devralma) ve denetim olayları.
+// Komutlar oturum döngüsüne kayıt üzerinden iletilir; her eylem `stream.supervisor.action` olarak yayınlanır.
+use super::admin::{self, error};
+use super::registry::{SessionCommand, SessionInfo};
+use crate::app::AppState;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use sentiric_ai_pipeline_sdk::PipelineInputEvent;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// Pipeline'a giden fısıltı metninin öneki. İstemciden bu önekle gelen metinler reddedilir.
+pub const HINT_MARKER: &str = "[SUPERVISOR_HINT]";
+/// Oturum döngüsünün komutu işlemesi için beklenecek süre
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+/// Fısıltıya verilen AI yanıtının arayandan gizlendiği en uzun süre
+const HINT_REPLY_MAX: Duration = Duration::from_secs(30);
+/// Yanıtın kesin transkriptinden sonra kesilen TTS'in son parçaları için pay
+const HINT_REPLY_TAIL: Duration = Duration::from_secs(1);
+/// Arayanın transkript göndericisi; diğer göndericiler AI yanıtıdır
+const CALLER_SENDER: &str = "user";
+const SUPERVISOR_ACTIONS: &str = "stream_gateway_supervisor_actions_total";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupervisorAction {
+    /// Metni yalnızca AI pipeline'ına ipucu olarak ilet; AI'nin ipucuna yanıtı kullanıcıya gitmez
+    Whisper,
+    /// AI konuşmasını kes ve konuşmayı devral (AI'ya giriş/çıkış durur)
+    BargeIn,
+    /// Devralınmış oturumda kullanıcıya metin mesajı gönder
+    Message,
+    /// Devralmayı bitir, AI kaldığı yerden devam eder
+    Release,
+}
+
+impl SupervisorAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Whisper => "whisper",
+            Self::BargeIn => "barge_in",
+            Self::Message => "message",
+            Self::Release => "release",
+        }
+    }
+
+    fn needs_text(self) -> bool {
+        matches!(self, Self::Whisper | Self::Message)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SupervisorRequest {
+    pub action: SupervisorAction,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Oturum döngüsüne iletilen doğrulanmış komut.
+pub struct SupervisorCommand {
+    pub action: SupervisorAction,
+    pub text: String,
+    /// Yönetim belirtecinin sahibi; denetim kaydına yazılır
+    pub supervisor: String,
+    /// Döngü işledikten sonra devralma durumunu ya da komutun neden uygulanmadığını döner
+    pub reply: oneshot::Sender<Result<bool, &'static str>>,
+}
+
+/// Fısıltının pipeline girdisi. Arayan girdisi işlenmiyorken (onay bekleniyor/reddedildi, anons
+/// çalıyor) süpervizör de pipeline'ı süremez.
+pub fn whisper_input(text: &str, accepts_input: bool) -> Result<PipelineInputEvent, &'static str> {
+    if !accepts_input {
+        return Err("caller input is not being processed (consent pending or declined, or disclosure playing)");
+    }
+    Ok(PipelineInputEvent::Text(format!(
+        "{} {}",
+        HINT_MARKER, text
+    )))
+}
+
+/// SDK'nın pipeline girdisi yalnızca ses ve metin taşır; sistem/bağlam girdisi yoktur. Fısıltı bu
+/// yüzden işaretli bir metin turu olarak gider ve diyalog geçmişine girer; AI'nin bu tura verdiği
+/// yanıt (ses ve AI transkripti) arayana, kayda ve altyazıya iletilmez. Yanıt AI'nin kesin
+/// transkriptiyle biter, kalan TTS kesilir; transkript gelmezse susturma `HINT_REPLY_MAX` sonra düşer.
+#[derive(Debug, Default)]
+pub struct HintReply {
+    until: Option<Instant>,
+}
+
+impl HintReply {
+    pub fn start(&mut self, now: Instant) {
+        self.until = Some(now + HINT_REPLY_MAX);
+    }
+
+    /// AI çıktısı şu an arayandan gizlenmeli mi.
+    pub fn mutes(&mut self, now: Instant) -> bool {
+        if self.until.is_some_and(|until| now >= until) {
+            self.until = None;
+        }
+        self.until.is_some()
+    }
+
+    /// AI transkripti susturulan yanıta aitse `true`. Kesin transkript yanıtı bitirir; ardından
+    /// yalnızca kesilen TTS'in son parçaları için kısa bir süre daha susturulur.
+    pub fn hides_transcript(&mut self, sender: &str, is_final: bool, now: Instant) -> bool {
+        if sender == CALLER_SENDER || !self.mutes(now) {
+            return false;
+        }
+        if is_final {
+            self.until = Some(now + HINT_REPLY_TAIL);
+        }
+        true
+    }
+}
+
+/// `POST /admin/sessions/{session_id}/supervisor`
+pub async fn command(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<SupervisorRequest>,
+) -> Response {
+    let supervisor = match admin::authenticate(&state, admin::bearer(&headers)) {
+        Ok(principal) => principal,
+        Err(denied) => return denied.into_response(),
+    };
+    let text = request.text.unwrap_or_default().trim().to_string();
+    if request.action.needs_text() && text.is_empty() {
+        return error(
+            StatusCode::BAD_REQUEST,
+            &format!("'{}' requires a non-empty text", request.action.as_str()),
+        );
+    }
+    let Some(entry) = state.sessions.get(&session_id) else {
+        return error(StatusCode::NOT_FOUND, "session not found");
+    };
+
+    let (reply, rx) = oneshot::channel();
+    let command = SupervisorCommand {
+        action: request.action,
+        text,
+        supervisor,
+        reply,
+    };
+    if let Err(resp) = admin::deliver(&entry, SessionCommand::Supervisor(command)).await {
+        return resp;
+    }
+    match tokio::time::timeout(COMMAND_TIMEOUT, rx).await {
+        Ok(Ok(Ok(takeover))) => Json(json!({
+            "session_id": session_id,
+            "action": request.action.as_str(),
+            "takeover": takeover,
+        }))
+        .into_response(),
+        Ok(Ok(Err(reason))) => error(StatusCode::CONFLICT, reason),
+        Ok(Err(_)) => error(StatusCode::GONE, "session already ended"),
+        // Döngü meşgul (ör. pipeline yeniden kuruluyor); komut sırada, sonra işlenecek
+        Err(_) => (
+            StatusCode::ACCEPTED,
+            Json(json!({
+                "session_id": session_id,
+                "action": request.action.as_str(),
+                "status": "queued",
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// Süpervizör eylemini denetim olayı olarak yayınlar (dinleme başlangıç/bitişi dahil).
+pub async fn publish_audit(
+    state: &AppState,
+    info: &SessionInfo,
+    supervisor: &str,
+    action: &str,
+    detail: Value,
+) {
+    let now = chrono::Utc::now();
+    let event = json!({
+        "event_type": "stream.supervisor.action",
+        "trace_id": info.trace_id,
+        "call_id": info.session_id,
+        "tenant_id": info.tenant_id,
+        "timestamp": now.to_rfc3339(),
+        "supervisor": supervisor,
+        "action": action,
+        "detail": detail,
+    });
+    state
+        .ghost_publisher
+        .publish_json("stream.supervisor.action", event)
+        .await;
+    state
+        .metrics
+        .incr(SUPERVISOR_ACTIONS, &[("action", action)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whisper_needs_caller_consent() {
+        assert!(whisper_input("offer a discount", false).is_err());
+        match whisper_input("offer a discount", true) {
+            Ok(PipelineInputEvent::Text(text)) => {
+                assert_eq!(text, "[SUPERVISOR_HINT] offer a discount")
+            }
+            _ => panic!("whisper must become a text turn"),
+        }
+    }
+
+    #[test]
+    fn hint_reply_is_muted_until_the_ai_turn_ends() {
+        let now = Instant::now();
+        let mut reply = HintReply::default();
+        assert!(!reply.mutes(now));
+        assert!(!reply.hides_transcript("ai", true, now));
+
+        reply.start(now);
+        assert!(reply.mutes(now));
+        // Arayanın konuşması gizlenmez
+        assert!(!reply.hides_transcript("user", true, now));
+        assert!(reply.hides_transcript("ai", false, now));
+        assert!(reply.hides_transcript("ai", true, now));
+        // Kesilen TTS'in son parçaları da gizlenir, sonra AI çıktısı yeniden akar
+        assert!(reply.mutes(now + HINT_REPLY_TAIL / 2));
+        assert!(!reply.mutes(now + HINT_REPLY_TAIL));
+        assert!(!reply.hides_transcript("ai", true, now + HINT_REPLY_TAIL));
+    }
+
+    #[test]
+    fn hint_reply_mute_expires_without_a_transcript() {
+        let now = Instant::now();
+        let mut reply = HintReply::default();
+        reply.start(now);
+        assert!(reply.mutes(now + HINT_REPLY_MAX - Duration::from_millis(1)));
+        assert!(!reply.mutes(now + HINT_REPLY_MAX));
+    }
+}
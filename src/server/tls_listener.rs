@@ -0,0 +1,115 @@
+// [YENİ]: HTTP/WS dinleyicisi için yerel TLS (wss) sonlandırma
+use crate::config::ListenerTlsConfig;
+use crate::tls::file_fingerprint;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+pub fn build_server_config(cfg: &ListenerTlsConfig) -> Result<ServerConfig, String> {
+    let mut provider = rustls::crypto::ring::default_provider();
+    if !cfg.cipher_suites.is_empty() {
+        provider
+            .cipher_suites
+            .retain(|cs| cfg.cipher_suites.contains(&format!("{:?}", cs.suite())));
+    }
+    let provider = Arc::new(provider);
+
+    let versions: &[&'static rustls::SupportedProtocolVersion] = match cfg.min_version.as_str() {
+        "1.3" => &[&rustls::version::TLS13],
+        _ => &[&rustls::version::TLS13, &rustls::version::TLS12],
+    };
+
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_protocol_versions(versions)
+        .map_err(|e| format!("server.tls: unsupported version/cipher combination: {}", e))?;
+
+    let builder = if cfg.client_ca_path.trim().is_empty() {
+        builder.with_no_client_auth()
+    } else {
+        let mut roots = RootCertStore::empty();
+        for cert in read_certs(&cfg.client_ca_path)? {
+            roots
+                .add(cert)
+                .map_err(|e| format!("server.tls.client_ca_path: invalid CA: {}", e))?;
+        }
+        let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+        let verifier = if cfg.require_client_cert {
+            verifier
+        } else {
+            verifier.allow_unauthenticated()
+        };
+        builder.with_client_cert_verifier(
+            verifier
+                .build()
+                .map_err(|e| format!("server.tls: client verifier could not be built: {}", e))?,
+        )
+    };
+
+    let certs = read_certs(&cfg.cert_path)?;
+    let key = {
+        let file = std::fs::File::open(&cfg.key_path)
+            .map_err(|e| format!("server.tls.key_path '{}': {}", cfg.key_path, e))?;
+        rustls_pemfile::private_key(&mut BufReader::new(file))
+            .map_err(|e| format!("server.tls.key_path '{}': {}", cfg.key_path, e))?
+            .ok_or_else(|| {
+                format!(
+                    "server.tls.key_path '{}': no private key found",
+                    cfg.key_path
+                )
+            })?
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| format!("server.tls: certificate/key mismatch: {}", e))?;
+    // WebSocket yükseltmesi yalnızca HTTP/1.1 üzerinde destekleniyor
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+fn read_certs(path: &str) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("'{}': {}", path, e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("'{}': invalid PEM: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("'{}': no certificate found", path));
+    }
+    Ok(certs)
+}
+
+/// Dinleyici sertifikaları da rotasyona uğrar; değiştiğinde açık bağlantıları koparmadan yenilenir.
+pub fn watch(rustls_config: RustlsConfig, cfg: ListenerTlsConfig, interval: Duration) {
+    tokio::spawn(async move {
+        let paths = [
+            cfg.cert_path.clone(),
+            cfg.key_path.clone(),
+            cfg.client_ca_path.clone(),
+        ];
+        let mut last_seen = file_fingerprint(&paths);
+        loop {
+            tokio::time::sleep(interval).await;
+            let current = file_fingerprint(&paths);
+            if current == last_seen {
+                continue;
+            }
+            last_seen = current;
+            match build_server_config(&cfg) {
+                Ok(next) => {
+                    rustls_config.reload_from_config(Arc::new(next));
+                    info!(
+                        event = "LISTENER_TLS_RELOADED",
+                        "Listener TLS certificate reloaded."
+                    );
+                }
+                Err(e) => {
+                    error!(event = "LISTENER_TLS_RELOAD_FAIL", error = %e, "Listener TLS reload failed. Keeping the current certificate.");
+                }
+            }
+        }
+    });
+}
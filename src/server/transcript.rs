@@ -0,0 +1,525 @@
+// [YENİ]: Oturum transkriptleri. Kesinleşmiş transkriptler oturum boyunca biriktirilir ve oturum
+// bittikten sonra `transcripts.retention_secs` boyunca bellekte tutulur; sayfayı yenileyen istemci
+// konuşmayı `GET /sessions/{id}/transcript` ile JSON, düz metin, WebVTT veya SRT olarak geri alır.
+use super::admin;
+use super::auth::Access;
+use crate::app::AppState;
+use crate::config::TranscriptConfig;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use sentiric_contracts::sentiric::stream::v1::TranscriptEvent;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+const TRANSCRIPT_REQUESTS: &str = "stream_gateway_transcript_requests_total";
+/// Altyazı satırının ekranda kalacağı en kısa süre
+pub const MIN_CUE_MS: u64 = 500;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Word {
+    pub word: String,
+    /// Sözce içindeki saniye (pipeline'ın verdiği haliyle)
+    pub start: f32,
+    pub end: f32,
+    pub probability: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Segment {
+    pub index: usize,
+    pub sender: String,
+    pub text: String,
+    pub emotion: String,
+    pub gender: String,
+    /// Oturum başından itibaren milisaniye
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub received_at: DateTime<Utc>,
+    pub words: Vec<Word>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Transcript {
+    pub session_id: String,
+    pub trace_id: String,
+    pub tenant_id: String,
+    pub user_id: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub segments: Vec<Segment>,
+    /// Konuşmacı başına ilk ara transkriptin zamanı; sözcenin başlangıcı sayılır
+    #[serde(skip)]
+    open_turns: HashMap<String, u64>,
+}
+
+impl Transcript {
+    fn offset_ms(&self, at: DateTime<Utc>) -> u64 {
+        (at - self.started_at).num_milliseconds().max(0) as u64
+    }
+
+    /// Ara transkript sözce başlangıcını işaretler; kesin transkript segment olarak eklenir.
+    pub fn observe(&mut self, event: &TranscriptEvent) {
+        let now = Utc::now();
+        let at = self.offset_ms(now);
+        if !event.is_final {
+            self.open_turns.entry(event.sender.clone()).or_insert(at);
+            return;
+        }
+        let opened = self.open_turns.remove(&event.sender);
+        let text = single_line(&event.text);
+        if text.is_empty() {
+            return;
+        }
+        let spoken_ms = match (event.words.first(), event.words.last()) {
+            (Some(first), Some(last)) if last.end > first.start => {
+                ((last.end - first.start) * 1000.0) as u64
+            }
+            _ => 0,
+        };
+        let start_ms = opened.unwrap_or_else(|| at.saturating_sub(spoken_ms));
+        self.segments.push(Segment {
+            index: self.segments.len() + 1,
+            sender: event.sender.clone(),
+            text,
+            emotion: event.emotion.clone(),
+            gender: event.gender.clone(),
+            start_ms,
+            end_ms: at.max(start_ms + MIN_CUE_MS),
+            received_at: now,
+            words: event
+                .words
+                .iter()
+                .map(|w| Word {
+                    word: w.word.clone(),
+                    start: w.start,
+                    end: w.end,
+                    probability: w.probability,
+                })
+                .collect(),
+        });
+    }
+}
+
+/// Oturum döngüsü ile HTTP ucunun paylaştığı transkript.
+pub type SharedTranscript = Arc<Mutex<Transcript>>;
+
+pub fn lock(transcript: &SharedTranscript) -> MutexGuard<'_, Transcript> {
+    transcript.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+struct Stored {
+    transcript: SharedTranscript,
+    /// Bu transkripte yazan canlı oturum sayısı (aynı session_id ile yeniden bağlanma)
+    live: usize,
+    ended: Option<Instant>,
+    /// Bu session_id ile kurulan bağlantılara verilen erişim belirteçleri
+    access_tokens: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct TranscriptStore {
+    entries: Mutex<HashMap<String, Stored>>,
+}
+
+impl TranscriptStore {
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, Stored>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Aynı session_id ile yeniden bağlanan istemci mevcut transkripte devam eder ve bağlantının
+    /// erişim belirteci transkripte eklenir. Transkript başka bir kullanıcıya aitse `None`.
+    pub fn open(
+        &self,
+        cfg: &TranscriptConfig,
+        session_id: &str,
+        trace_id: &str,
+        tenant_id: &str,
+        user_id: &str,
+        access_token: &str,
+    ) -> Option<SharedTranscript> {
+        let mut entries = self.entries();
+        prune(&mut entries, cfg);
+        if let Some(stored) = entries.get(session_id) {
+            if lock(&stored.transcript).user_id != user_id {
+                return None;
+            }
+        }
+        let stored = entries
+            .entry(session_id.to_string())
+            .or_insert_with(|| Stored {
+                transcript: Arc::new(Mutex::new(Transcript {
+                    session_id: session_id.to_string(),
+                    trace_id: trace_id.to_string(),
+                    tenant_id: tenant_id.to_string(),
+                    user_id: user_id.to_string(),
+                    started_at: Utc::now(),
+                    ended_at: None,
+                    segments: Vec::new(),
+                    open_turns: HashMap::new(),
+                })),
+                live: 0,
+                ended: None,
+                access_tokens: Vec::new(),
+            });
+        stored.live += 1;
+        stored.ended = None;
+        stored.access_tokens.push(access_token.to_string());
+        lock(&stored.transcript).ended_at = None;
+        Some(stored.transcript.clone())
+    }
+
+    /// Son canlı oturum bittiğinde transkripti kapatır ve anlık görüntüsünü döner.
+    pub fn close(&self, cfg: &TranscriptConfig, session_id: &str) -> Option<Transcript> {
+        let mut entries = self.entries();
+        let stored = entries.get_mut(session_id)?;
+        stored.live = stored.live.saturating_sub(1);
+        if stored.live > 0 {
+            return None;
+        }
+        stored.ended = Some(Instant::now());
+        let snapshot = {
+            let mut transcript = lock(&stored.transcript);
+            transcript.ended_at = Some(Utc::now());
+            transcript.open_turns.clear();
+            transcript.clone()
+        };
+        prune(&mut entries, cfg);
+        Some(snapshot)
+    }
+
+    /// Erişim bu oturuma açık değilse transkript yokmuş gibi `None` döner.
+    pub fn get(
+        &self,
+        cfg: &TranscriptConfig,
+        session_id: &str,
+        access: &Access,
+    ) -> Option<Transcript> {
+        let mut entries = self.entries();
+        prune(&mut entries, cfg);
+        entries
+            .get(session_id)
+            .filter(|stored| access.allows(&stored.access_tokens))
+            .map(|stored| lock(&stored.transcript).clone())
+    }
+}
+
+/// Süresi dolan ve sınırı aşan biten oturumları atar (en eski önce).
+fn prune(entries: &mut HashMap<String, Stored>, cfg: &TranscriptConfig) {
+    let retention = Duration::from_secs(cfg.retention_secs);
+    entries.retain(|_, stored| stored.ended.is_none_or(|at| at.elapsed() < retention));
+    let mut ended: Vec<_> = entries
+        .iter()
+        .filter_map(|(id, stored)| stored.ended.map(|at| (at, id.clone())))
+        .collect();
+    if ended.len() <= cfg.max_sessions {
+        return;
+    }
+    ended.sort();
+    for (_, id) in ended.iter().take(ended.len() - cfg.max_sessions) {
+        entries.remove(id);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    Json,
+    Text,
+    WebVtt,
+    Srt,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "txt" | "text" => Some(Self::Text),
+            "vtt" | "webvtt" => Some(Self::WebVtt),
+            "srt" => Some(Self::Srt),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Text => "txt",
+            Self::WebVtt => "vtt",
+            Self::Srt => "srt",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Text => "text/plain; charset=utf-8",
+            Self::WebVtt => "text/vtt; charset=utf-8",
+            Self::Srt => "application/x-subrip; charset=utf-8",
+        }
+    }
+}
+
+pub fn render(transcript: &Transcript, format: Format) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Json => return serde_json::to_string(transcript).unwrap_or_default(),
+        Format::Text => {
+            for s in &transcript.segments {
+                let _ = writeln!(
+                    out,
+                    "[{}] {}: {}",
+                    clock(s.start_ms, None),
+                    s.sender,
+                    s.text
+                );
+            }
+        }
+        Format::WebVtt => {
+            out.push_str("WEBVTT\n\n");
+            for s in &transcript.segments {
+                let _ = write!(
+                    out,
+                    "{}\n{} --> {}\n<v {}>{}\n\n",
+                    s.index,
+                    clock(s.start_ms, Some('.')),
+                    clock(s.end_ms, Some('.')),
+                    vtt_escape(&s.sender),
+                    vtt_escape(&s.text)
+                );
+            }
+        }
+        Format::Srt => {
+            for s in &transcript.segments {
+                let _ = write!(
+                    out,
+                    "{}\n{} --> {}\n{}: {}\n\n",
+                    s.index,
+                    clock(s.start_ms, Some(',')),
+                    clock(s.end_ms, Some(',')),
+                    s.sender,
+                    s.text
+                );
+            }
+        }
+    }
+    out
+}
+
+/// `hh:mm:ss`, ayraç verilirse milisaniyeyle (`hh:mm:ss.mmm` / `hh:mm:ss,mmm`).
+pub fn clock(ms: u64, millis_separator: Option<char>) -> String {
+    let secs = ms / 1000;
+    let base = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    match millis_separator {
+        Some(sep) => format!("{}{}{:03}", base, sep, ms % 1000),
+        None => base,
+    }
+}
+
+/// Boş satır altyazı biçimlerinde satırı bitirir; metin tek satıra indirilir.
+pub fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn vtt_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub async fn get_transcript(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    let live_config = state.current_config();
+    // Belirteç yalnızca başlıkla kabul edilir; sorgu dizesi erişim loglarına düşer
+    let Some(token) = admin::bearer(&headers) else {
+        warn!(
+            event = "TRANSCRIPT_AUTH_FAILED",
+            "Transcript request without a token."
+        );
+        return admin::error(StatusCode::UNAUTHORIZED, "missing token");
+    };
+    let access = Access::resolve(&live_config, token);
+    if !live_config.transcripts.enabled {
+        return admin::error(StatusCode::NOT_FOUND, "transcripts are disabled");
+    }
+    let format = match params.get("format") {
+        None => Format::Json,
+        Some(value) => match Format::parse(value) {
+            Some(format) => format,
+            None => {
+                return admin::error(
+                    StatusCode::BAD_REQUEST,
+                    "format must be one of json, txt, vtt, srt",
+                )
+            }
+        },
+    };
+    // Başka oturumun belirteci, olmayan oturumla aynı yanıtı alır
+    let Some(transcript) = state
+        .transcripts
+        .get(&live_config.transcripts, &session_id, &access)
+    else {
+        return admin::error(StatusCode::NOT_FOUND, "transcript not found");
+    };
+    state
+        .metrics
+        .incr(TRANSCRIPT_REQUESTS, &[("format", format.as_str())]);
+    (
+        [(header::CONTENT_TYPE, format.content_type())],
+        render(&transcript, format),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(retention_secs: u64, max_sessions: usize) -> TranscriptConfig {
+        TranscriptConfig {
+            enabled: true,
+            retention_secs,
+            max_sessions,
+        }
+    }
+
+    fn segment(index: usize, sender: &str, text: &str, start_ms: u64, end_ms: u64) -> Segment {
+        Segment {
+            index,
+            sender: sender.to_string(),
+            text: text.to_string(),
+            emotion: String::new(),
+            gender: String::new(),
+            start_ms,
+            end_ms,
+            received_at: Utc::now(),
+            words: Vec::new(),
+        }
+    }
+
+    fn sample() -> Transcript {
+        Transcript {
+            session_id: "s1".into(),
+            trace_id: "t1".into(),
+            tenant_id: "default".into(),
+            user_id: "u1".into(),
+            started_at: Utc::now(),
+            ended_at: None,
+            segments: vec![
+                segment(1, "user", "Merhaba <dünya> & hoş geldin", 1_200, 2_500),
+                segment(
+                    2,
+                    "ai",
+                    "Size nasıl yardımcı olabilirim?",
+                    3_723_004,
+                    3_725_000,
+                ),
+            ],
+            open_turns: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn clock_formats_with_and_without_millis() {
+        assert_eq!(clock(0, None), "00:00:00");
+        assert_eq!(clock(3_723_004, None), "01:02:03");
+        assert_eq!(clock(3_723_004, Some('.')), "01:02:03.004");
+        assert_eq!(clock(59_999, Some(',')), "00:00:59,999");
+    }
+
+    #[test]
+    fn renders_vtt_with_voice_tags_and_escaping() {
+        let vtt = render(&sample(), Format::WebVtt);
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n\
+             1\n00:00:01.200 --> 00:00:02.500\n<v user>Merhaba &lt;dünya&gt; &amp; hoş geldin\n\n\
+             2\n01:02:03.004 --> 01:02:05.000\n<v ai>Size nasıl yardımcı olabilirim?\n\n"
+        );
+    }
+
+    #[test]
+    fn renders_srt_and_text() {
+        let transcript = sample();
+        assert_eq!(
+            render(&transcript, Format::Srt),
+            "1\n00:00:01,200 --> 00:00:02,500\nuser: Merhaba <dünya> & hoş geldin\n\n\
+             2\n01:02:03,004 --> 01:02:05,000\nai: Size nasıl yardımcı olabilirim?\n\n"
+        );
+        assert_eq!(
+            render(&transcript, Format::Text),
+            "[00:00:01] user: Merhaba <dünya> & hoş geldin\n\
+             [01:02:03] ai: Size nasıl yardımcı olabilirim?\n"
+        );
+    }
+
+    #[test]
+    fn access_is_bound_to_issued_tokens() {
+        let store = TranscriptStore::default();
+        let cfg = cfg(3600, 10);
+        assert!(store
+            .open(&cfg, "s1", "t1", "default", "u1", "tok-a")
+            .is_some());
+        // Aynı kullanıcının yeniden bağlanması yeni belirteci ekler
+        assert!(store
+            .open(&cfg, "s1", "t2", "default", "u1", "tok-b")
+            .is_some());
+        // Başka kullanıcı aynı session_id'yi ele geçiremez
+        assert!(store
+            .open(&cfg, "s1", "t3", "default", "u2", "tok-c")
+            .is_none());
+
+        let session = |t: &str| Access::Session(t.to_string());
+        assert!(store.get(&cfg, "s1", &session("tok-a")).is_some());
+        assert!(store.get(&cfg, "s1", &session("tok-b")).is_some());
+        assert!(store.get(&cfg, "s1", &session("tok-c")).is_none());
+        assert!(store
+            .get(&cfg, "s1", &Access::Admin("ops".into()))
+            .is_some());
+        assert!(store
+            .get(&cfg, "missing", &Access::Admin("ops".into()))
+            .is_none());
+    }
+
+    #[test]
+    fn prune_drops_expired_and_oldest_ended_sessions() {
+        let admin = Access::Admin("ops".into());
+        let store = TranscriptStore::default();
+        let expired = cfg(0, 10);
+        store.open(&expired, "gone", "t", "default", "u", "tok");
+        store.open(&expired, "live", "t", "default", "u", "tok");
+        assert!(store.close(&expired, "gone").is_some());
+        // Canlı oturum saklama süresinden bağımsız tutulur
+        assert!(store.get(&expired, "gone", &admin).is_none());
+        assert!(store.get(&expired, "live", &admin).is_some());
+
+        let store = TranscriptStore::default();
+        let bounded = cfg(3600, 2);
+        for id in ["a", "b", "c"] {
+            store.open(&bounded, id, "t", "default", "u", "tok");
+            store.close(&bounded, id);
+        }
+        assert!(store.get(&bounded, "a", &admin).is_none());
+        assert!(store.get(&bounded, "b", &admin).is_some());
+        assert!(store.get(&bounded, "c", &admin).is_some());
+    }
+
+    #[test]
+    fn reconnect_keeps_transcript_open_until_last_close() {
+        let store = TranscriptStore::default();
+        let cfg = cfg(3600, 10);
+        store.open(&cfg, "s1", "t1", "default", "u1", "tok-a");
+        store.open(&cfg, "s1", "t2", "default", "u1", "tok-b");
+        assert!(store.close(&cfg, "s1").is_none());
+        let closed = store.close(&cfg, "s1").expect("last close snapshots");
+        assert!(closed.ended_at.is_some());
+    }
+}
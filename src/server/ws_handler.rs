@@ -1,17 +1,23 @@
 use axum::{
     extract::ws::{Message, WebSocket, WebSocketUpgrade},
     extract::State,
-    response::Response,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
 };
 use prost::Message as ProstMessage;
 use serde_json::json;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use axum::extract::Query;
+use axum::extract::{ConnectInfo, Query};
+use axum::http::{header, HeaderMap};
 use sentiric_ai_pipeline_sdk::config::SdkConfig;
 use sentiric_ai_pipeline_sdk::orchestrator::PipelineOrchestrator;
 use sentiric_ai_pipeline_sdk::{PipelineEvent, PipelineInputEvent};
@@ -19,22 +25,228 @@ use sentiric_contracts::sentiric::event::v1::CognitiveMapUpdatedEvent;
 use sentiric_contracts::sentiric::stream::v1::stream_session_request::Data as ReqData;
 use sentiric_contracts::sentiric::stream::v1::stream_session_response::Data as RespData;
 use sentiric_contracts::sentiric::stream::v1::{
-    SessionConfig, StreamSessionRequest, StreamSessionResponse, TranscriptEvent, WordData,
+    SessionConfig, StreamSessionRequest, TranscriptEvent, WordData,
 };
 
 use crate::app::AppState;
+use crate::audio::resample::bytes_to_samples;
+use crate::audio::vad::{EnergyVad, PlaybackTracker, VadEvent};
+use crate::audio::{self, SessionTranscoder};
+use crate::config::{AppConfig, LimitsConfig};
+use crate::server::admission::{AdmissionState, AdmissionTicket, Overload};
+use crate::server::auth;
+use crate::server::captions::{CaptionFeed, Captioner};
+use crate::server::consent::{self, Consent, Disclosure};
+use crate::server::control::{self, ControlEvent};
+use crate::server::export::Exporter;
+use crate::server::flow_control::{InboundGuard, Violation};
+use crate::server::lifecycle::{ClientMeta, EndReason, SessionStats};
+use crate::server::monitor::{self, SessionMonitor};
+use crate::server::pipeline_error::{self, PipelineFailure};
+use crate::server::profile::{self, EffectiveSession};
+use crate::server::quota::{self, QuotaDenied, Scope, SessionPermit};
+use crate::server::recording::{Recorder, Track};
+use crate::server::registry::{SessionCommand, SessionEntry, SessionInfo};
+use crate::server::session_error::{close_with_error, SessionError};
+use crate::server::socket::{self, Inbound, Outbound};
+use crate::server::supervisor::{self, HintReply, SupervisorAction, SupervisorCommand};
+use crate::server::transcript::{self, SharedTranscript};
+
+const HANDSHAKE_FAILURES: &str = "stream_gateway_handshake_failures_total";
+const ACTIVE_SESSIONS: &str = "stream_gateway_active_sessions";
+const SESSIONS_ENDED: &str = "stream_gateway_sessions_ended_total";
+const INBOUND_VIOLATIONS: &str = "stream_gateway_inbound_violations_total";
+const QUOTA_REJECTIONS: &str = "stream_gateway_quota_rejections_total";
+const ADMISSION_REJECTIONS: &str = "stream_gateway_admission_rejections_total";
+const RECORDINGS: &str = "stream_gateway_recordings_total";
+const EXPORTS: &str = "stream_gateway_exports_total";
+const CONSENT_DECISIONS: &str = "stream_gateway_consent_decisions_total";
 
 pub async fn ws_upgrade(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
     Query(params): Query<HashMap<String, String>>,
+    ConnectInfo(remote): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
 ) -> Response {
-    let trace_id = params
-        .get("trace_id")
-        .cloned()
-        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let live_config = state.current_config();
+    // [YENİ]: Tarayıcılar WS'te başlık gönderemediği için `?token=` de kabul edilir
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string())
+        .or_else(|| params.get("token").cloned());
+    let client_ip = quota::client_ip(
+        remote.ip(),
+        headers.get("x-forwarded-for").and_then(|v| v.to_str().ok()),
+        live_config.limits.trusted_proxy_hops,
+    );
+    let Admitted {
+        user_id,
+        admission,
+        permit,
+    } = match admit(&state, &live_config, token.as_deref(), client_ip) {
+        Ok(admitted) => admitted,
+        Err(Rejected::Unauthorized) => return auth_rejected(&state),
+        Err(Rejected::Overloaded(overloaded)) => {
+            return admission_rejected(&state, overloaded, live_config.admission.retry_after_secs)
+        }
+        Err(Rejected::Quota(denied)) => return quota_rejected(&state, denied),
+    };
+    let limits = live_config.limits.clone();
+
+    let handshake = HandshakeParams {
+        client: ClientMeta::from_request(remote, &headers, &params),
+        trace_id: params
+            .get("trace_id")
+            .cloned()
+            .unwrap_or_else(|| Uuid::new_v4().to_string()),
+        // [YENİ]: İstemcinin seçtiği isimli oturum profili
+        profile: params.get("profile").cloned(),
+        // [YENİ]: İstemci ses formatı müzakeresi (örn. ?codec=opus&codec_rate=48000)
+        codec: params.get("codec").cloned(),
+        codec_rate: params.get("codec_rate").cloned(),
+        user_id,
+        // [YENİ]: Arayan onayı istemci tarafında önceden alındıysa (örn. IVR) `?consent=granted`
+        consent: params
+            .get("consent")
+            .is_some_and(|v| matches!(v.as_str(), "granted" | "true" | "1" | "yes")),
+        permit,
+        limits,
+        admission,
+    };
 
-    ws.on_upgrade(move |socket| handle_websocket(socket, state, trace_id))
+    ws.on_upgrade(move |socket| handle_websocket(socket, state, handshake))
+}
+
+/// Yükseltme öncesi kabulün sonucu; bilet ve izin oturum boyunca tutulur.
+struct Admitted {
+    user_id: Option<String>,
+    admission: AdmissionTicket,
+    permit: SessionPermit,
+}
+
+enum Rejected {
+    Unauthorized,
+    Overloaded(AdmissionState),
+    Quota(QuotaDenied),
+}
+
+/// Sıra önemlidir: önce belirteç, sonra genel kabul, en son kotalar. Geçersiz belirteçli istek
+/// hiçbir sayaca dokunmadan `HTTP 401` alır; aksi halde tenant'ın dakikalık kotasını tüketip
+/// geçerli kullanıcıları dışarıda bırakabilirdi.
+fn admit(
+    state: &Arc<AppState>,
+    live_config: &AppConfig,
+    token: Option<&str>,
+    client_ip: String,
+) -> Result<Admitted, Rejected> {
+    let tokens = &live_config.auth.tokens;
+    if !tokens.is_empty() && !token.is_some_and(|t| auth::matches_any(tokens, t)) {
+        return Err(Rejected::Unauthorized);
+    }
+    // [YENİ]: Süreç aşırı yüklüyse yeni oturum alınmaz; mevcut oturumların gerçek zamanlılığı korunur
+    let admission = state
+        .admission
+        .try_admit(&live_config.admission, &state.metrics)
+        .map_err(Rejected::Overloaded)?;
+    // Kullanıcı istemcinin beyanından değil doğrulanmış belirteçten türetilir
+    let user_id = token.and_then(|t| auth::principal(tokens, t));
+    // [YENİ]: Kota kontrolü yükseltmeden önce; reddedilen istemci için orkestratör hiç kurulmaz
+    let mut quota_keys = vec![
+        (Scope::Tenant, state.config.tenant_id.clone()),
+        (Scope::Ip, client_ip),
+    ];
+    if let Some(user) = &user_id {
+        quota_keys.push((Scope::User, user.clone()));
+    }
+    let permit = state
+        .quotas
+        .try_acquire(quota_keys, &live_config.limits)
+        .map_err(Rejected::Quota)?;
+    Ok(Admitted {
+        user_id,
+        admission,
+        permit,
+    })
+}
+
+/// WebSocket yükseltme isteğinin sorgu parametreleri.
+pub struct HandshakeParams {
+    trace_id: String,
+    profile: Option<String>,
+    codec: Option<String>,
+    codec_rate: Option<String>,
+    /// Doğrulanmış belirtecin sahibi; kimlik doğrulama kapalıysa yok
+    user_id: Option<String>,
+    consent: bool,
+    /// Oturum bitene kadar tutulur; düşürülünce eşzamanlı oturum sayacı azalır
+    permit: SessionPermit,
+    limits: LimitsConfig,
+    admission: AdmissionTicket,
+    client: ClientMeta,
+}
+
+fn auth_rejected(state: &AppState) -> Response {
+    state
+        .metrics
+        .incr(HANDSHAKE_FAILURES, &[("reason", "auth_failure")]);
+    warn!(
+        event = "WS_AUTH_FAILED",
+        "Upgrade rejected, missing or invalid access token."
+    );
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({ "error": "auth_failure", "reason": "missing or invalid access token." })),
+    )
+        .into_response()
+}
+
+/// [YENİ]: Aşırı yükte yükseltme HTTP 503 + `Retry-After` ile reddedilir.
+fn admission_rejected(state: &AppState, admission: AdmissionState, retry_after: u64) -> Response {
+    let reason = admission.reason.map(Overload::as_str).unwrap_or_default();
+    state
+        .metrics
+        .incr(ADMISSION_REJECTIONS, &[("reason", reason)]);
+    warn!(
+        event = "SESSION_ADMISSION_REJECTED",
+        reason = reason,
+        "Upgrade rejected, gateway overloaded."
+    );
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(header::RETRY_AFTER, retry_after.to_string())],
+        Json(json!({ "error": "overloaded", "admission": admission })),
+    )
+        .into_response()
+}
+
+/// [YENİ]: Kota aşımı yükseltme aşamasında HTTP 429 ile reddedilir.
+fn quota_rejected(state: &AppState, denied: QuotaDenied) -> Response {
+    state.metrics.incr(
+        QUOTA_REJECTIONS,
+        &[
+            ("scope", denied.scope.as_str()),
+            ("limit", denied.limit.as_str()),
+        ],
+    );
+    warn!(
+        event = "SESSION_QUOTA_REJECTED",
+        scope = denied.scope.as_str(),
+        limit = denied.limit.as_str(),
+        "Upgrade rejected by session quota."
+    );
+    let body = Json(json!({ "error": "quota_exceeded", "quota": denied }));
+    match denied.retry_after_secs {
+        Some(secs) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, secs.to_string())],
+            body,
+        )
+            .into_response(),
+        None => (StatusCode::TOO_MANY_REQUESTS, body).into_response(),
+    }
 }
 
 struct SessionContext {
@@ -43,30 +255,100 @@ struct SessionContext {
     tenant_id: String,
     session_id: String,
     user_id: String,
+    effective: EffectiveSession,
+    /// Handshake'te seçilen profil; yeniden yapılandırmada da aynı katmanlar uygulanır.
+    profile_name: Option<String>,
+    /// İstemcinin en son kabul edilen `SessionConfig` değeri.
+    requested: SessionConfig,
+    client: ClientMeta,
 }
 
-pub async fn handle_websocket(
-    mut socket: WebSocket,
+pub async fn handle_websocket(socket: WebSocket, state: Arc<AppState>, handshake: HandshakeParams) {
+    // [YENİ]: Oturum boyunca sabit kalan konfigürasyon anlık görüntüsü (hot-reload güvenli)
+    let live_config = state.current_config();
+    // [YENİ]: Okuma ve yazma ayrı görevlerde; yavaş istemci oturum döngüsünü bloklamaz
+    let (out, mut inbound, socket_tasks) = socket::split(
+        socket,
+        live_config.session.inbound_queue_capacity,
+        live_config.session.outbound_queue_capacity,
+        state.metrics.clone(),
+    );
+    run_session(&out, &mut inbound, state, live_config, handshake).await;
+    socket_tasks.finish(&out).await;
+}
+
+async fn run_session(
+    out: &Outbound,
+    inbound: &mut Inbound,
     state: Arc<AppState>,
-    initial_trace_id: String,
+    live_config: Arc<AppConfig>,
+    handshake: HandshakeParams,
 ) {
+    let initial_trace_id = handshake.trace_id;
     let span_id = Uuid::new_v4().to_string();
     let tenant_id = state.config.tenant_id.clone();
 
-    let session_config = match wait_for_config(&mut socket, &initial_trace_id).await {
-        Some(config) => config,
-        None => {
-            let _ = socket.close().await;
+    let handshake_timeout = Duration::from_millis(live_config.session.handshake_timeout_ms);
+    let session_config = match tokio::time::timeout(handshake_timeout, wait_for_config(inbound))
+        .await
+    {
+        Ok(Ok(Some(config))) => config,
+        Ok(Ok(None)) => {
+            warn!(event = "WS_CONNECTION_DROPPED_EARLY", trace_id = %initial_trace_id, "Client disconnected during handshake.");
+            state
+                .metrics
+                .incr(HANDSHAKE_FAILURES, &[("reason", "client_disconnected")]);
             return;
         }
+        Ok(Err(err)) => return fail_session(out, &state, &initial_trace_id, err).await,
+        Err(_) => {
+            return fail_session(
+                out,
+                &state,
+                &initial_trace_id,
+                SessionError::HandshakeTimeout,
+            )
+            .await
+        }
     };
 
-    let session_ctx = SessionContext {
-        trace_id: if !session_config.trace_id.is_empty() {
-            session_config.trace_id.clone()
-        } else {
-            initial_trace_id
-        },
+    let trace_id = if !session_config.trace_id.is_empty() {
+        session_config.trace_id.clone()
+    } else {
+        initial_trace_id
+    };
+
+    let effective =
+        match profile::resolve(&live_config, handshake.profile.as_deref(), &session_config) {
+            Ok(effective) => effective,
+            Err(reason) => {
+                return fail_session(out, &state, &trace_id, SessionError::ConfigRejected(reason))
+                    .await
+            }
+        };
+
+    // [YENİ]: Codec müzakeresi; pipeline her zaman PCM16 görür.
+    let transcoder = match audio::negotiate(
+        handshake.codec.as_deref(),
+        handshake.codec_rate.as_deref(),
+        live_config.audio.pipeline_input_sample_rate,
+    )
+    .and_then(|format| {
+        SessionTranscoder::new(
+            format,
+            live_config.audio.pipeline_input_sample_rate,
+            effective.sample_rate,
+            live_config.audio.opus_frame_ms,
+        )
+    }) {
+        Ok(t) => t,
+        Err(reason) => {
+            return fail_session(out, &state, &trace_id, SessionError::ConfigRejected(reason)).await
+        }
+    };
+
+    let mut session_ctx = SessionContext {
+        trace_id,
         span_id,
         tenant_id,
         session_id: if !session_config.session_id.is_empty() {
@@ -74,47 +356,204 @@ pub async fn handle_websocket(
         } else {
             Uuid::new_v4().to_string()
         },
-        user_id: "stream-client".to_string(),
+        user_id: handshake
+            .user_id
+            .unwrap_or_else(|| "stream-client".to_string()),
+        effective,
+        profile_name: handshake.profile,
+        requested: session_config,
+        client: handshake.client,
     };
 
     info!(event = "WS_CONNECTION_ESTABLISHED", trace_id = %session_ctx.trace_id, span_id = %session_ctx.span_id, "Session authenticated and ready.");
 
-    publish_call_started(&state, &session_ctx).await;
+    // [YENİ]: Onay beklenirken hiçbir şey işlenmez veya saklanmaz
+    let consent = Consent::new(&live_config.consent, handshake.consent);
+    if consent.required && !consent.is_pending() {
+        state.metrics.incr(
+            CONSENT_DECISIONS,
+            &[("result", "granted"), ("source", "handshake")],
+        );
+    }
+    publish_call_started(&state, &session_ctx, &consent).await;
+    let disclosure = load_disclosure(&live_config, &session_ctx).await;
+    let stats = SessionStats::default();
 
-    let sdk_config = build_sdk_config(&state.config, &session_config);
-    let orchestrator = match PipelineOrchestrator::new(sdk_config).await {
-        Ok(orch) => orch,
+    let mut pipeline = match start_pipeline(&live_config, &session_ctx).await {
+        Ok(pipeline) => pipeline,
         Err(e) => {
             error!(event = "ORCHESTRATOR_INIT_FAIL", trace_id = %session_ctx.trace_id, error = %e, "Failed to init AI Pipeline.");
-            let _ = socket.close().await;
-            return;
+            publish_call_ended(
+                &state,
+                &session_ctx,
+                EndReason::OrchestratorInitFailed,
+                &stats,
+            )
+            .await;
+            return fail_session(
+                out,
+                &state,
+                &session_ctx.trace_id,
+                SessionError::OrchestratorInit,
+            )
+            .await;
         }
     };
 
-    let (rx_input_tx, rx_input_rx) = mpsc::channel::<PipelineInputEvent>(128);
-    let (tx_out_tx, mut tx_out_rx) = mpsc::channel(128);
-    let (interrupt_tx, interrupt_rx) = mpsc::channel(10);
+    state.metrics.add_gauge(ACTIVE_SESSIONS, &[], 1.0);
 
-    spawn_orchestrator(
-        orchestrator,
-        &session_ctx,
-        rx_input_rx,
-        tx_out_tx,
-        interrupt_rx,
+    // [YENİ]: Bu bağlantıya özel erişim belirteci; transkript yalnızca bununla ya da yönetim
+    // belirteciyle okunabilir
+    let access_token = auth::session_token();
+    let transcript = live_config
+        .transcripts
+        .enabled
+        .then(|| {
+            let opened = state.transcripts.open(
+                &live_config.transcripts,
+                &session_ctx.session_id,
+                &session_ctx.trace_id,
+                &session_ctx.tenant_id,
+                &session_ctx.user_id,
+                &access_token,
+            );
+            if opened.is_none() {
+                warn!(event = "TRANSCRIPT_OWNER_MISMATCH", trace_id = %session_ctx.trace_id, session_id = %session_ctx.session_id, "Session id belongs to another user's transcript; transcript disabled for this connection.");
+            }
+            opened
+        })
+        .flatten();
+    let caption_feed = live_config
+        .captions
+        .enabled
+        .then(|| CaptionFeed::new(live_config.captions.buffer_cues));
+    let mut media = SessionMedia {
+        transcoder,
+        vad: live_config.vad.enabled.then(|| {
+            EnergyVad::new(
+                &live_config.vad,
+                live_config.audio.pipeline_input_sample_rate,
+            )
+        }),
+        barge_in: live_config.vad.barge_in,
+        report_vad: live_config.vad.report_to_client,
+        playback: PlaybackTracker::new(session_ctx.effective.sample_rate),
+        muted: false,
+        paused: false,
+        input_rate: live_config.audio.pipeline_input_sample_rate,
+        flow: InboundGuard::new(&live_config.audio),
+        quota: handshake.permit,
+        _admission: handshake.admission,
+        monitor: SessionMonitor::new(live_config.admin.monitor_buffer_frames),
+        takeover: false,
+        recorder: consent
+            .allows_processing()
+            .then(|| start_recording(&live_config, &session_ctx))
+            .flatten(),
+        exporter: consent
+            .allows_processing()
+            .then(|| start_export(&live_config, &session_ctx))
+            .flatten(),
+        transcript,
+        captions: caption_feed.clone().map(Captioner::new),
+        consent,
+        disclosure,
+        limits: handshake.limits,
+        restart_at: None,
+        hint_reply: HintReply::default(),
+        stats,
+    };
+
+    let format_json = json!({
+        "type": "AUDIO_FORMAT",
+        "client": media.transcoder.client_format(),
+        "pipeline_input_sample_rate": live_config.audio.pipeline_input_sample_rate,
+        "pipeline_output_sample_rate": session_ctx.effective.sample_rate,
+        "supported_codecs": audio::AudioCodec::supported(),
+    })
+    .to_string();
+    out.send(RespData::StatusUpdate(format_json));
+    if media.transcript.is_some() || caption_feed.is_some() {
+        let path = |resource: &str| format!("/sessions/{}/{}", session_ctx.session_id, resource);
+        let access_json = json!({
+            "type": "SESSION_ACCESS",
+            "token": &access_token,
+            "transcript": media.transcript.is_some().then(|| path("transcript")),
+            "captions": caption_feed.is_some().then(|| path("captions")),
+        })
+        .to_string();
+        out.send(RespData::StatusUpdate(access_json));
+    }
+    // [YENİ]: İstemci hangi kontrol sinyallerini gönderebileceğini buradan öğrenir
+    out.send(RespData::StatusUpdate(control::capabilities().to_string()));
+    if media.consent.is_pending() {
+        let required = consent::required_message(&live_config.consent, media.disclosure.as_ref());
+        out.send(RespData::StatusUpdate(required.to_string()));
+        // Anons varsa süre anons bitince başlar
+        if media.disclosure.is_none() {
+            media.consent.arm(live_config.consent.timeout_secs);
+        }
+    }
+
+    // [YENİ]: Yönetim API'si için oturum kaydı; döngü bitince kayıt silinir
+    let info = SessionInfo {
+        session_id: session_ctx.session_id.clone(),
+        trace_id: session_ctx.trace_id.clone(),
+        tenant_id: session_ctx.tenant_id.clone(),
+        user_id: session_ctx.user_id.clone(),
+        profile: session_ctx.profile_name.clone(),
+        client: session_ctx.client.clone(),
+    };
+    let (entry, mut commands) = SessionEntry::new(
+        info,
+        out.clone(),
+        media.monitor.clone(),
+        caption_feed,
+        access_token,
     );
+    let registration = state.sessions.register(entry);
 
     let mut cognitive_rx = state.cognitive_tx.subscribe();
     let mut media_rx = state.media_tx.subscribe(); // [YENİ] Media Receiver
 
-    loop {
+    // [YENİ]: Keepalive / boşta kalma / azami süre denetimi
+    let session_limits = &live_config.session;
+    let mut liveness = Liveness::new();
+    let mut keepalive = (session_limits.keepalive_interval_secs > 0).then(|| {
+        let period = Duration::from_secs(session_limits.keepalive_interval_secs);
+        tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+    });
+    let max_duration = async {
+        match session_limits.max_duration_secs {
+            0 => std::future::pending().await,
+            secs => tokio::time::sleep(Duration::from_secs(secs)).await,
+        }
+    };
+    tokio::pin!(max_duration);
+    let mut disclosure_tick = tokio::time::interval(Duration::from_millis(100));
+
+    let end_reason = loop {
         tokio::select! {
-            ws_msg = socket.recv() => {
-                if !process_client_message(ws_msg, &rx_input_tx, &interrupt_tx, &state, &session_ctx).await {
-                    break;
+            ws_msg = inbound.recv() => {
+                liveness.last_seen = Instant::now();
+                if let Some(Ok(Message::Binary(bin))) = &ws_msg {
+                    registration.entry().add_bytes_in(bin.len());
+                }
+                match process_client_message(ws_msg, &pipeline, &state, &session_ctx, &mut media, &mut liveness, out).await {
+                    LoopAction::Continue => {}
+                    LoopAction::Close(reason) => break reason,
+                    LoopAction::Reconfigure(next) => {
+                        reconfigure(next, &live_config, &mut session_ctx, &mut pipeline, &mut media, out).await;
+                    }
+                    LoopAction::Consent(granted) => {
+                        if let Some(reason) = apply_consent(granted, &state, &live_config, &session_ctx, &mut media, out).await {
+                            break reason;
+                        }
+                    }
                 }
             }
             cog_event_res = cognitive_rx.recv() => {
-                process_cognitive_map(cog_event_res, &session_ctx.trace_id, &mut socket).await;
+                process_cognitive_map(cog_event_res, &session_ctx.trace_id, out).await;
             }
             // [YENİ]: RabbitMQ'dan gelen Video Üretim Sonucunu Tarayıcıya Gönder
             media_event = media_rx.recv() => {
@@ -127,60 +566,599 @@ pub async fn handle_websocket(
                             "uri": evt.result_uri,
                             "error_message": evt.error_message
                         }).to_string();
-                        send_ws_response(&mut socket, RespData::StatusUpdate(status_json)).await;
+                        out.send(RespData::StatusUpdate(status_json));
+                    }
+                }
+            }
+            // Yeniden kurulum beklenirken eski pipeline'ın kapanmış kanalı dinlenmez
+            ai_event = pipeline.output_rx.recv(), if media.restart_at.is_none() => {
+                if let LoopAction::Close(reason) = process_ai_event(ai_event, &pipeline.interrupt_tx, &state, &session_ctx, out, &mut media).await {
+                    if reason != EndReason::PipelineCompleted {
+                        break reason;
                     }
+                    match pipeline.outcome().await {
+                        Ok(()) => break EndReason::PipelineCompleted,
+                        Err(failure) => {
+                            if !schedule_pipeline_restart(failure, &live_config, &session_ctx, &mut media, out) {
+                                break EndReason::PipelineError;
+                            }
+                        }
+                    }
+                }
+            }
+            _ = async {
+                match media.restart_at {
+                    Some(at) => tokio::time::sleep_until(at).await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                media.restart_at = None;
+                if !restart_pipeline(&live_config, &session_ctx, &mut pipeline, &mut media, out).await {
+                    break EndReason::PipelineError;
                 }
             }
-            ai_event = tx_out_rx.recv() => {
-                if !process_ai_event(ai_event, &state, &session_ctx, &mut socket).await {
-                    break;
+            _ = async {
+                match keepalive.as_mut() {
+                    Some(interval) => interval.tick().await,
+                    None => std::future::pending().await,
                 }
+            } => {
+                if liveness.last_seen.elapsed() > Duration::from_secs(session_limits.pong_timeout_secs) {
+                    warn!(event = "WS_PONG_TIMEOUT", trace_id = %session_ctx.trace_id, "No frames from client within pong deadline. Dropping half-open connection.");
+                    break EndReason::PongTimeout;
+                }
+                if !out.ping() {
+                    break EndReason::ClientDisconnected;
+                }
+            }
+            // Boşta kalma süresi ping'den bağımsızdır; keepalive kapalıyken de işler
+            _ = tokio::time::sleep_until(tokio::time::Instant::from_std(
+                liveness.last_activity + Duration::from_secs(session_limits.idle_timeout_secs),
+            )), if session_limits.idle_timeout_secs > 0 => {
+                info!(event = "SESSION_IDLE_TIMEOUT", trace_id = %session_ctx.trace_id, "No client audio/text within idle timeout.");
+                close_with_error(out, &SessionError::IdleTimeout);
+                break EndReason::IdleTimeout;
+            }
+            Some(command) = commands.recv() => match command {
+                SessionCommand::Inspect(reply) => {
+                    let _ = reply.send(inspect_session(&session_ctx, &media));
+                }
+                SessionCommand::Supervisor(command) => {
+                    handle_supervisor(command, &pipeline, &state, registration.entry(), &mut media, out).await;
+                }
+                SessionCommand::Terminate => break admin_terminate(&session_ctx.trace_id, out),
+            },
+            // [YENİ]: Bilgilendirme anonsu gerçek zamanlı hızda istemciye çalınır
+            _ = disclosure_tick.tick(), if media.disclosure.is_some() => {
+                if let LoopAction::Close(reason) = play_disclosure(&live_config, &session_ctx, &mut media, out) {
+                    break reason;
+                }
+            }
+            _ = consent::expired(media.consent.deadline()), if media.consent.is_pending() => {
+                info!(event = "CONSENT_TIMEOUT", trace_id = %session_ctx.trace_id, "Caller consent not given in time.");
+                state.metrics.incr(CONSENT_DECISIONS, &[("result", "timeout"), ("source", "timeout")]);
+                close_with_error(out, &SessionError::ConsentTimeout);
+                break EndReason::ConsentTimeout;
+            }
+            _ = out.overrun() => {
+                warn!(event = "WS_SLOW_CONSUMER", trace_id = %session_ctx.trace_id, stats = ?out.stats(), "Client is not reading. Outbound queue hit its hard limit.");
+                close_with_error(out, &SessionError::SlowConsumer);
+                break EndReason::SlowConsumer;
+            }
+            _ = &mut max_duration => {
+                info!(event = "SESSION_MAX_DURATION", trace_id = %session_ctx.trace_id, "Maximum session duration reached.");
+                close_with_error(out, &SessionError::MaxDurationExceeded);
+                break EndReason::MaxDurationExceeded;
             }
         }
+    };
+
+    pipeline.stop();
+    state.metrics.add_gauge(ACTIVE_SESSIONS, &[], -1.0);
+    let transcode_stats = media.transcoder.stats();
+    info!(event = "TRANSCODER_STATS", trace_id = %session_ctx.trace_id, codec = ?media.transcoder.client_format().codec, inbound_chunks = transcode_stats.inbound_chunks, outbound_chunks = transcode_stats.outbound_chunks, inbound_cpu_us = transcode_stats.inbound_cpu_us, outbound_cpu_us = transcode_stats.outbound_cpu_us, "Session transcoding cost.");
+
+    media.stats.outbound = Some(out.stats());
+    if let Some(recorder) = media.recorder.take() {
+        finish_recording(&state, &session_ctx, recorder).await;
+    }
+    if let Some(exporter) = media.exporter.take() {
+        // Son parçaların yüklenmesi (yeniden denemeler dahil) oturum kapanışını bekletmez
+        tokio::spawn(finish_export(
+            state.clone(),
+            session_ctx.trace_id.clone(),
+            exporter,
+        ));
     }
+    if media.transcript.take().is_some() {
+        finish_transcript(&state, &live_config, &session_ctx).await;
+    }
+    publish_call_ended(&state, &session_ctx, end_reason, &media.stats).await;
+}
 
-    publish_call_ended(&state, &session_ctx).await;
-    let _ = socket.close().await;
+/// [YENİ]: Transkripti kapatır ve `transcript.completed` olayını yayınlar. Aynı session_id ile
+/// başka bir bağlantı hâlâ sürüyorsa transkript açık kalır.
+async fn finish_transcript(state: &AppState, live_config: &AppConfig, ctx: &SessionContext) {
+    let Some(transcript) = state
+        .transcripts
+        .close(&live_config.transcripts, &ctx.session_id)
+    else {
+        return;
+    };
+    info!(event = "TRANSCRIPT_COMPLETED", trace_id = %ctx.trace_id, segments = transcript.segments.len(), "Session transcript closed.");
+    let event = json!({
+        "event_type": "transcript.completed",
+        "trace_id": ctx.trace_id,
+        "call_id": ctx.session_id,
+        "tenant_id": ctx.tenant_id,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "segments": transcript.segments.len(),
+        "transcript": transcript,
+    });
+    state
+        .ghost_publisher
+        .publish_json("transcript.completed", event)
+        .await;
 }
 
-// --- HELPER FUNCTIONS ---
+/// [YENİ]: Kayıt açıksa yazıcıyı başlatır; başlatılamazsa oturum kayıtsız devam eder.
+fn start_recording(live_config: &AppConfig, ctx: &SessionContext) -> Option<Recorder> {
+    if !live_config.recording.enabled {
+        return None;
+    }
+    match Recorder::start(&live_config.recording, &ctx.session_id) {
+        Ok(recorder) => Some(recorder),
+        Err(e) => {
+            error!(event = "RECORDING_START_FAIL", trace_id = %ctx.trace_id, error = %e, "Session recording could not be started.");
+            None
+        }
+    }
+}
 
-// ORIGINAL LOGS RESTORED
-async fn wait_for_config(socket: &mut WebSocket, trace_id: &str) -> Option<SessionConfig> {
-    if let Some(Ok(Message::Binary(bin))) = socket.recv().await {
-        match StreamSessionRequest::decode(&bin[..]) {
-            Ok(req) => {
-                if let Some(ReqData::Config(config)) = req.data {
-                    return Some(config);
-                } else {
-                    error!(event = "INVALID_FIRST_MESSAGE", trace_id = %trace_id, "First message MUST be SessionConfig.");
+async fn finish_recording(state: &AppState, ctx: &SessionContext, recorder: Recorder) {
+    match recorder.finish().await {
+        Ok(summary) => {
+            state.metrics.incr(RECORDINGS, &[("result", "completed")]);
+            info!(event = "RECORDING_COMPLETED", trace_id = %ctx.trace_id, path = %summary.wav_path, duration_secs = summary.duration_secs, dropped = summary.dropped, "Session recording written.");
+            let event = json!({
+                "event_type": "recording.completed",
+                "trace_id": ctx.trace_id,
+                "call_id": ctx.session_id,
+                "tenant_id": ctx.tenant_id,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "recording": summary,
+            });
+            state
+                .ghost_publisher
+                .publish_json("recording.completed", event)
+                .await;
+        }
+        Err(e) => {
+            state.metrics.incr(RECORDINGS, &[("result", "failed")]);
+            error!(event = "RECORDING_FAIL", trace_id = %ctx.trace_id, error = %e, "Session recording failed.");
+        }
+    }
+}
+
+/// [YENİ]: Dışa aktarım açıksa yükleme görevini başlatır; başlatılamazsa oturum dışa aktarımsız devam eder.
+fn start_export(live_config: &AppConfig, ctx: &SessionContext) -> Option<Exporter> {
+    if !live_config.export.enabled {
+        return None;
+    }
+    match Exporter::start(
+        &live_config.export,
+        &ctx.tenant_id,
+        &ctx.session_id,
+        &ctx.trace_id,
+    ) {
+        Ok(exporter) => Some(exporter),
+        Err(e) => {
+            error!(event = "EXPORT_START_FAIL", trace_id = %ctx.trace_id, error = %e, "Session export could not be started.");
+            None
+        }
+    }
+}
+
+async fn finish_export(state: Arc<AppState>, trace_id: String, exporter: Exporter) {
+    match exporter.finish().await {
+        Ok(summary) => {
+            let result = summary.result();
+            state.metrics.incr(EXPORTS, &[("result", result)]);
+            info!(event = "EXPORT_COMPLETED", trace_id = %trace_id, root = %summary.root, result = result, dropped = summary.dropped, "Session export finished.");
+            let event = json!({
+                "event_type": "export.completed",
+                "trace_id": summary.trace_id,
+                "call_id": summary.session_id,
+                "tenant_id": summary.tenant_id,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "result": result,
+                "export": summary,
+            });
+            state
+                .ghost_publisher
+                .publish_json("export.completed", event)
+                .await;
+        }
+        Err(e) => {
+            state.metrics.incr(EXPORTS, &[("result", "failed")]);
+            error!(event = "EXPORT_FAIL", trace_id = %trace_id, error = %e, "Session export failed.");
+        }
+    }
+}
+
+/// [YENİ]: Anons dosyası tanımlıysa yükler; yüklenemezse oturum anonssuz devam eder.
+async fn load_disclosure(live_config: &AppConfig, ctx: &SessionContext) -> Option<Disclosure> {
+    let path = &live_config.consent.disclosure_audio_path;
+    if path.is_empty() {
+        return None;
+    }
+    match Disclosure::load(path, ctx.effective.sample_rate).await {
+        Ok(disclosure) => Some(disclosure),
+        Err(e) => {
+            error!(event = "CONSENT_DISCLOSURE_LOAD_FAIL", trace_id = %ctx.trace_id, error = %e, "Disclosure audio could not be loaded.");
+            None
+        }
+    }
+}
+
+/// [YENİ]: Anonsun sıradaki parçasını gönderir; anons bitince onay süresi başlar.
+fn play_disclosure(
+    live_config: &AppConfig,
+    ctx: &SessionContext,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) -> LoopAction {
+    let Some(disclosure) = media.disclosure.as_mut() else {
+        return LoopAction::Continue;
+    };
+    let chunk = disclosure.next_chunk();
+    let done = disclosure.is_done();
+    if let Some(chunk) = chunk {
+        media.playback.on_audio_sent(chunk.len());
+        media
+            .monitor
+            .audio(monitor::Channel::Agent, &chunk, ctx.effective.sample_rate);
+        media.capture(Track::Ai, &chunk, ctx.effective.sample_rate);
+        if let LoopAction::Close(reason) = send_audio(chunk, ctx, media, out) {
+            return LoopAction::Close(reason);
+        }
+    }
+    if done {
+        media.disclosure = None;
+        media.consent.arm(live_config.consent.timeout_secs);
+        info!(event = "CONSENT_DISCLOSURE_PLAYED", trace_id = %ctx.trace_id, "Disclosure played to caller.");
+        media.emit(|| json!({ "type": "DISCLOSURE_PLAYED" }));
+        let status_json = json!({
+            "type": "DISCLOSURE_COMPLETED",
+            "consent": media.consent.state,
+        })
+        .to_string();
+        return delivered(out.send(RespData::StatusUpdate(status_json)));
+    }
+    LoopAction::Continue
+}
+
+/// [YENİ]: Onay kararını uygular. Onay verilirse kayıt/dışa aktarım başlar; reddedilirse oturum kapanır.
+async fn apply_consent(
+    granted: bool,
+    state: &Arc<AppState>,
+    live_config: &AppConfig,
+    ctx: &SessionContext,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) -> Option<EndReason> {
+    if !media.consent.decide(granted, "control") {
+        return None;
+    }
+    let result = if granted { "granted" } else { "declined" };
+    info!(event = "CONSENT_DECIDED", trace_id = %ctx.trace_id, result = result, "Caller consent decided.");
+    state.metrics.incr(
+        CONSENT_DECISIONS,
+        &[("result", result), ("source", "control")],
+    );
+    if granted {
+        media.recorder = start_recording(live_config, ctx);
+        media.exporter = start_export(live_config, ctx);
+    }
+    let decision = media.consent.state;
+    media.emit(|| json!({ "type": "CONSENT", "consent": decision }));
+    let status_json = json!({ "type": "CONSENT_UPDATED", "consent": media.consent }).to_string();
+    out.send(RespData::StatusUpdate(status_json));
+
+    let event = json!({
+        "event_type": "stream.consent.updated",
+        "trace_id": ctx.trace_id,
+        "call_id": ctx.session_id,
+        "tenant_id": ctx.tenant_id,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "consent": media.consent,
+    });
+    state
+        .ghost_publisher
+        .publish_json("stream.consent.updated", event)
+        .await;
+
+    if granted {
+        return None;
+    }
+    close_with_error(out, &SessionError::ConsentDeclined);
+    Some(EndReason::ConsentDeclined)
+}
+
+/// İstemcinin formatına kodlayıp AI sesini gönderir.
+fn send_audio(
+    chunk: Vec<u8>,
+    ctx: &SessionContext,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) -> LoopAction {
+    match media.transcoder.outbound(chunk) {
+        Ok(packets) => {
+            for packet in packets {
+                if !out.send(RespData::AudioResponse(packet)) {
+                    return LoopAction::Close(EndReason::ClientDisconnected);
                 }
             }
-            Err(e) => {
-                error!(event = "PROTOBUF_DECODE_ERROR", trace_id = %trace_id, error = %e, "Failed to decode config.")
+            LoopAction::Continue
+        }
+        Err(e) => {
+            warn!(event = "AUDIO_ENCODE_ERROR", trace_id = %ctx.trace_id, error = %e, "Outbound audio chunk dropped.");
+            LoopAction::Continue
+        }
+    }
+}
+
+/// İstemciden son çerçeve (pong dahil) ve son ses/metin zamanları.
+struct Liveness {
+    last_seen: Instant,
+    last_activity: Instant,
+}
+
+impl Liveness {
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            last_seen: now,
+            last_activity: now,
+        }
+    }
+}
+
+/// Oturumun ses işleme durumu (dönüştürme, VAD, TTS oynatma takibi).
+struct SessionMedia {
+    transcoder: SessionTranscoder,
+    vad: Option<EnergyVad>,
+    barge_in: bool,
+    report_vad: bool,
+    playback: PlaybackTracker,
+    /// İstemci mikrofonu susturuldu: gelen ses pipeline'a iletilmez.
+    muted: bool,
+    /// Pipeline duraklatıldı: ne ses ne metin iletilir.
+    paused: bool,
+    /// Pipeline'a giden PCM16 sesin örnekleme hızı (ses süresi hesabı için)
+    input_rate: u32,
+    /// [YENİ]: Gelen ses parça/hız sınırları
+    flow: InboundGuard,
+    quota: SessionPermit,
+    /// Yalnızca tutulur; oturum bitince kabul sayacı düşer
+    _admission: AdmissionTicket,
+    /// [YENİ]: Süpervizör dinleme aynası (dinleyici yoksa maliyetsiz)
+    monitor: SessionMonitor,
+    /// [YENİ]: Süpervizör konuşmayı devraldı; AI'ya giriş gitmez, AI sesi istemciye iletilmez
+    takeover: bool,
+    /// [YENİ]: Uyum kaydı (kapalıysa `None`)
+    recorder: Option<Recorder>,
+    /// [YENİ]: Nesne depolamaya dışa aktarım (kapalıysa `None`)
+    exporter: Option<Exporter>,
+    /// [YENİ]: HTTP ucuyla paylaşılan kesin transkriptler (kapalıysa `None`)
+    transcript: Option<SharedTranscript>,
+    /// [YENİ]: Canlı altyazı üreticisi (kapalıysa `None`)
+    captions: Option<Captioner>,
+    /// [YENİ]: Arayan onayı; verilene kadar girdi pipeline'a gitmez, kayıt/dışa aktarım başlamaz
+    consent: Consent,
+    /// [YENİ]: Çalınmakta olan bilgilendirme anonsu (bitince `None`)
+    disclosure: Option<Disclosure>,
+    /// Yükseltme anındaki kota sınırları
+    limits: LimitsConfig,
+    /// [YENİ]: Çöken pipeline'ın yeniden kurulacağı an; bu sürede ses pipeline'a iletilmez
+    restart_at: Option<tokio::time::Instant>,
+    /// [YENİ]: Süpervizör fısıltısına verilen AI yanıtı arayandan gizlenir
+    hint_reply: HintReply,
+    stats: SessionStats,
+}
+
+impl SessionMedia {
+    /// Arayan girdisi yalnızca onay verildikten ve anons bittikten sonra işlenir.
+    fn accepts_input(&self) -> bool {
+        self.consent.allows_processing() && self.disclosure.is_none()
+    }
+
+    /// Olayı süpervizör dinleyicilerine, kayıt yan dosyasına ve dışa aktarıma iletir.
+    fn emit(&mut self, build: impl Fn() -> serde_json::Value) {
+        self.monitor.event(&build);
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.event(build());
+        }
+        if let Some(exporter) = self.exporter.as_mut() {
+            exporter.event(build());
+        }
+    }
+
+    /// Pipeline'a giden kullanıcı sesini ve istemciye giden AI sesini kayda ve dışa aktarıma yazar.
+    fn capture(&mut self, track: Track, pcm: &[u8], rate: u32) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.audio(track, pcm, rate);
+        }
+        if let Some(exporter) = self.exporter.as_mut() {
+            exporter.audio(track, pcm, rate);
+        }
+    }
+
+    /// İstemci tamponu temizlendiğinde henüz çalınmamış AI sesi kayıttan ve dışa aktarımdan da düşer.
+    fn clear_recorded_ai(&mut self) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.clear_ai();
+        }
+        if let Some(exporter) = self.exporter.as_mut() {
+            exporter.clear_ai();
+        }
+    }
+}
+
+/// Çalışan pipeline'a giden/gelen kanallar. Yeniden yapılandırmada bütünüyle değiştirilir.
+struct PipelineHandle {
+    input_tx: mpsc::Sender<PipelineInputEvent>,
+    interrupt_tx: mpsc::Sender<()>,
+    output_rx: mpsc::Receiver<PipelineEvent>,
+    task: JoinHandle<Result<(), PipelineFailure>>,
+}
+
+impl PipelineHandle {
+    fn stop(self) {
+        self.task.abort();
+    }
+
+    /// Çıkış kanalı kapandıktan sonra pipeline görevinin nasıl bittiğini döner.
+    async fn outcome(&mut self) -> Result<(), PipelineFailure> {
+        match (&mut self.task).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(failure)) => Err(failure),
+            Err(join_err) => Err(pipeline_error::aborted(&join_err.to_string())),
+        }
+    }
+}
+
+/// İstemci mesajı işlendikten sonra oturum döngüsünün ne yapacağı.
+enum LoopAction {
+    Continue,
+    Close(EndReason),
+    /// Yeni istemci konfigürasyonu ile pipeline yeniden kurulur.
+    Reconfigure(SessionConfig),
+    /// [YENİ]: Arayan onay verdi (`true`) veya reddetti.
+    Consent(bool),
+}
+
+/// [YENİ]: Handshake/başlatma hatası -> sayaç, log, yapılandırılmış yanıt ve kapanış kodu.
+async fn fail_session(out: &Outbound, state: &Arc<AppState>, trace_id: &str, err: SessionError) {
+    warn!(event = "SESSION_HANDSHAKE_FAILED", trace_id = %trace_id, code = err.code(), reason = %err.message(), "Session rejected during handshake.");
+    state
+        .metrics
+        .incr(HANDSHAKE_FAILURES, &[("reason", err.code())]);
+    close_with_error(out, &err);
+}
+
+// --- HELPER FUNCTIONS ---
+
+/// İlk anlamlı çerçeveyi bekler. `Ok(None)`: istemci handshake sırasında ayrıldı.
+async fn wait_for_config(inbound: &mut Inbound) -> Result<Option<SessionConfig>, SessionError> {
+    loop {
+        match inbound.recv().await {
+            // Ping/Pong çerçeveleri axum tarafından yanıtlanır; handshake'i etkilemez.
+            Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => continue,
+            Some(Ok(Message::Binary(bin))) => {
+                return match StreamSessionRequest::decode(&bin[..]) {
+                    Ok(StreamSessionRequest {
+                        data: Some(ReqData::Config(config)),
+                    }) => Ok(Some(config)),
+                    Ok(_) => Err(SessionError::WrongFirstMessage),
+                    Err(e) => Err(SessionError::BadProtobuf(e.to_string())),
+                };
             }
+            Some(Ok(Message::Text(_))) => return Err(SessionError::WrongFirstMessage),
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(None),
         }
-    } else {
-        warn!(event = "WS_CONNECTION_DROPPED_EARLY", trace_id = %trace_id, "Client disconnected during handshake.");
     }
-    None
 }
 
 async fn process_client_message(
     ws_msg: Option<Result<Message, axum::Error>>,
-    rx_input_tx: &mpsc::Sender<PipelineInputEvent>,
-    interrupt_tx: &mpsc::Sender<()>,
+    pipeline: &PipelineHandle,
     state: &Arc<AppState>,
     session_ctx: &SessionContext,
-) -> bool {
+    media: &mut SessionMedia,
+    liveness: &mut Liveness,
+    out: &Outbound,
+) -> LoopAction {
     match ws_msg {
         Some(Ok(Message::Binary(bin))) => {
             if let Ok(req) = StreamSessionRequest::decode(&bin[..]) {
                 match req.data {
                     Some(ReqData::AudioChunk(chunk)) => {
-                        let _ = rx_input_tx.send(PipelineInputEvent::Audio(chunk)).await;
+                        liveness.last_activity = Instant::now();
+                        if media.muted || media.paused {
+                            return LoopAction::Continue;
+                        }
+                        // [YENİ]: Çözmeden önce boyut/hizalama, çözdükten sonra süre ve hız sınırı
+                        let codec = media.transcoder.client_format().codec;
+                        if let Err(v) = media.flow.check_raw(chunk.len(), codec) {
+                            return flow_violation(v, state, session_ctx, media, out);
+                        }
+                        let pcm = match media.transcoder.inbound(chunk) {
+                            Ok(pcm) => pcm,
+                            Err(e) => {
+                                warn!(event = "AUDIO_DECODE_ERROR", trace_id = %session_ctx.trace_id, error = %e, "Inbound audio chunk dropped.");
+                                return flow_violation(
+                                    Violation::MalformedChunk,
+                                    state,
+                                    session_ctx,
+                                    media,
+                                    out,
+                                );
+                            }
+                        };
+                        let chunk_secs = SessionStats::pcm_seconds(pcm.len(), media.input_rate);
+                        if let Err(v) = media.flow.admit(chunk_secs) {
+                            return flow_violation(v, state, session_ctx, media, out);
+                        }
+                        if !media.accepts_input() {
+                            return LoopAction::Continue;
+                        }
+                        media.stats.audio_in_secs += chunk_secs;
+                        if let Some(denied) =
+                            state
+                                .quotas
+                                .record_audio(media.quota.keys(), chunk_secs, &media.limits)
+                        {
+                            return quota_exhausted(denied, state, session_ctx, out);
+                        }
+                        media
+                            .monitor
+                            .audio(monitor::Channel::Caller, &pcm, media.input_rate);
+                        media.capture(Track::User, &pcm, media.input_rate);
+                        if media.takeover || media.restart_at.is_some() {
+                            return LoopAction::Continue;
+                        }
+                        let vad_event = media
+                            .vad
+                            .as_mut()
+                            .and_then(|vad| vad.process(&bytes_to_samples(&pcm)));
+                        if pipeline
+                            .input_tx
+                            .send(PipelineInputEvent::Audio(pcm))
+                            .await
+                            .is_err()
+                        {
+                            warn!(event = "PIPELINE_INPUT_CLOSED", trace_id = %session_ctx.trace_id, "Pipeline input channel closed, audio chunk dropped.");
+                        }
+                        if let Some(vad_event) = vad_event {
+                            handle_vad_event(vad_event, pipeline, session_ctx, media, out).await;
+                        }
                     }
                     Some(ReqData::TextMessage(text)) => {
+                        liveness.last_activity = Instant::now();
+                        media.stats.text_messages_in += 1;
+                        // [YENİ]: Değer taşıyan kontroller (dil/ses değişimi) metin komutu olarak gelir
+                        if let Some((event, value)) = control::parse_text_control(&text) {
+                            return handle_control(
+                                event,
+                                Some(value),
+                                pipeline,
+                                session_ctx,
+                                media,
+                                out,
+                            )
+                            .await;
+                        }
                         // [YENİ]: Gizli Video Komutu Yakalayıcı (Bypass Pipeline)
                         if text.starts_with("[CMD:GENERATE_VIDEO]") {
                             let payload = text.replace("[CMD:GENERATE_VIDEO]", "");
@@ -188,9 +1166,12 @@ async fn process_client_message(
                             if parts.len() == 2 {
                                 let model = parts[0].trim().to_string();
                                 let prompt = parts[1].trim().to_string();
-                                let video_cli = state.video_client.clone();
+                                let video_cli = state.video_client();
                                 let t_id = session_ctx.trace_id.clone();
                                 let ten_id = session_ctx.tenant_id.clone();
+                                let duration_seconds = session_ctx.effective.video_duration_seconds;
+                                let aspect_ratio = session_ctx.effective.video_aspect_ratio.clone();
+                                let fps = session_ctx.effective.video_fps;
 
                                 tokio::spawn(async move {
                                     if let Some(mut cli) = video_cli {
@@ -201,41 +1182,404 @@ async fn process_client_message(
                                             prompt,
                                             preferred_model: model,
                                             reference_image_uri: None,
-                                            duration_seconds: 5,
-                                            aspect_ratio: "16:9".into(),
-                                            fps: 24,
+                                            duration_seconds,
+                                            aspect_ratio,
+                                            fps,
                                         });
                                         let _ = cli.submit_video_job(req).await;
                                     }
                                 });
                             }
-                            return true; // Pipeline'a atma, işlemi yut.
+                            return LoopAction::Continue; // Pipeline'a atma, işlemi yut.
                         }
-                        let _ = rx_input_tx.send(PipelineInputEvent::Text(text)).await;
-                    }
-                    Some(ReqData::Control(ctrl)) => {
-                        if ctrl.event == 1 {
-                            let _ = interrupt_tx.try_send(());
-                        } else if ctrl.event == 2 {
-                            let _ = rx_input_tx.try_send(PipelineInputEvent::Audio(vec![]));
+                        // [YENİ]: Süpervizör ipucu öneki yalnızca gateway tarafından eklenebilir
+                        if text.trim_start().starts_with(supervisor::HINT_MARKER) {
+                            warn!(event = "SPOOFED_SUPERVISOR_HINT", trace_id = %session_ctx.trace_id, "Client text with supervisor marker dropped.");
+                            return LoopAction::Continue;
+                        }
+                        if !media.accepts_input() {
+                            return LoopAction::Continue;
                         }
+                        media.emit(|| json!({ "type": "CALLER_TEXT", "text": text }));
+                        if !media.paused && !media.takeover {
+                            let _ = pipeline.input_tx.send(PipelineInputEvent::Text(text)).await;
+                        }
+                    }
+                    // [YENİ]: Oturum ortasında dil/ses/prompt değişimi
+                    Some(ReqData::Config(update)) => {
+                        return LoopAction::Reconfigure(profile::merge_update(
+                            &session_ctx.requested,
+                            &update,
+                        ));
                     }
+                    Some(ReqData::Control(ctrl)) => match ControlEvent::from_code(ctrl.event) {
+                        Some(event) => {
+                            return handle_control(event, None, pipeline, session_ctx, media, out)
+                                .await;
+                        }
+                        None => {
+                            warn!(event = "UNKNOWN_CONTROL_EVENT", trace_id = %session_ctx.trace_id, code = ctrl.event, "Unknown control event rejected.");
+                            send_control_error(
+                                out,
+                                ctrl.event,
+                                "unknown control event".to_string(),
+                            )
+                            .await;
+                        }
+                    },
                     _ => {}
                 }
             }
-            true
+            LoopAction::Continue
+        }
+        Some(Ok(Message::Close(_))) => LoopAction::Close(EndReason::ClientClosed),
+        Some(Err(_)) | None => LoopAction::Close(EndReason::ClientDisconnected),
+        _ => LoopAction::Continue,
+    }
+}
+
+/// [YENİ]: Tipli kontrol sinyali işleyicisi. `value` yalnızca metin komutlarından gelir.
+async fn handle_control(
+    event: ControlEvent,
+    value: Option<String>,
+    pipeline: &PipelineHandle,
+    ctx: &SessionContext,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) -> LoopAction {
+    let mut action = LoopAction::Continue;
+    match event {
+        ControlEvent::Interrupt => {
+            let _ = pipeline.interrupt_tx.try_send(());
+        }
+        ControlEvent::EndOfUtterance => {
+            // Ses yolundaki onay/devralma kapısı; aksi halde onaysız oturumda pipeline tetiklenir
+            if media.accepts_input() && !media.takeover {
+                let _ = pipeline
+                    .input_tx
+                    .try_send(PipelineInputEvent::Audio(vec![]));
+            }
+            if let Some(vad) = media.vad.as_mut() {
+                vad.reset();
+            }
+        }
+        ControlEvent::Mute | ControlEvent::PausePipeline => {
+            if event == ControlEvent::Mute {
+                media.muted = true;
+            } else {
+                media.paused = true;
+                let _ = pipeline.interrupt_tx.try_send(());
+            }
+            if let Some(vad) = media.vad.as_mut() {
+                vad.reset();
+            }
+        }
+        ControlEvent::Unmute => media.muted = false,
+        ControlEvent::ResumePipeline => media.paused = false,
+        ControlEvent::SwitchLanguage | ControlEvent::SwitchVoice => {
+            let Some(value) = value.filter(|v| !v.is_empty()) else {
+                let reason = format!(
+                    "{} requires a value; send it as a '{}<value>' text message.",
+                    event.name(),
+                    event.text_command().unwrap_or_default()
+                );
+                send_control_error(out, event.code(), reason).await;
+                return LoopAction::Continue;
+            };
+            let mut next = ctx.requested.clone();
+            if event == ControlEvent::SwitchLanguage {
+                next.language = value;
+            } else {
+                next.tts_voice_id = value;
+            }
+            // Onay, yeniden yapılandırma sonucunda (SESSION_RECONFIGURED) gönderilir.
+            return LoopAction::Reconfigure(next);
+        }
+        ControlEvent::Ping => {
+            let status_json =
+                json!({ "type": "PONG", "ts": chrono::Utc::now().timestamp_millis() }).to_string();
+            out.send(RespData::StatusUpdate(status_json));
+            return LoopAction::Continue;
+        }
+        ControlEvent::EndSession => {
+            info!(event = "SESSION_END_REQUESTED", trace_id = %ctx.trace_id, "Client requested session end.");
+            action = LoopAction::Close(EndReason::EndSessionRequested);
+        }
+        ControlEvent::GrantConsent | ControlEvent::DeclineConsent => {
+            action = LoopAction::Consent(event == ControlEvent::GrantConsent);
         }
-        Some(Ok(Message::Close(_))) | None => false,
-        _ => true,
+    }
+    media.emit(|| json!({ "type": "CONTROL", "control": event.name() }));
+    let status_json = json!({ "type": "CONTROL_ACK", "control": event.name() }).to_string();
+    out.send(RespData::StatusUpdate(status_json));
+    action
+}
+
+/// [YENİ]: Süpervizör komutunu uygular, istemciyi ve dinleyicileri bilgilendirir, denetim olayı yayınlar.
+async fn handle_supervisor(
+    command: SupervisorCommand,
+    pipeline: &PipelineHandle,
+    state: &AppState,
+    entry: &SessionEntry,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) {
+    let action = command.action;
+    match action {
+        SupervisorAction::Whisper => {
+            let hint = match supervisor::whisper_input(&command.text, media.accepts_input()) {
+                Ok(hint) => hint,
+                Err(reason) => {
+                    warn!(event = "SUPERVISOR_ACTION_REFUSED", trace_id = %entry.info.trace_id, supervisor = %command.supervisor, action = action.as_str(), reason = reason, "Supervisor hint refused.");
+                    let _ = command.reply.send(Err(reason));
+                    return;
+                }
+            };
+            if pipeline.input_tx.send(hint).await.is_err() {
+                warn!(event = "PIPELINE_INPUT_CLOSED", trace_id = %entry.info.trace_id, "Pipeline input channel closed, supervisor hint dropped.");
+            } else {
+                media.hint_reply.start(Instant::now());
+            }
+        }
+        SupervisorAction::BargeIn => {
+            let _ = pipeline.interrupt_tx.try_send(());
+            media.takeover = true;
+            media.transcoder.reset_outbound();
+            media.playback.clear();
+            if let Some(vad) = media.vad.as_mut() {
+                vad.reset();
+            }
+            out.send(RespData::ClearAudioBuffer(true));
+            media.clear_recorded_ai();
+        }
+        SupervisorAction::Message => {
+            let status_json =
+                json!({ "type": "SUPERVISOR_MESSAGE", "text": command.text }).to_string();
+            out.send(RespData::StatusUpdate(status_json));
+        }
+        SupervisorAction::Release => media.takeover = false,
+    }
+    if matches!(
+        action,
+        SupervisorAction::BargeIn | SupervisorAction::Release
+    ) {
+        let status_json = json!({ "type": "TAKEOVER", "active": media.takeover }).to_string();
+        out.send(RespData::StatusUpdate(status_json));
+    }
+
+    info!(event = "SUPERVISOR_ACTION", trace_id = %entry.info.trace_id, supervisor = %command.supervisor, action = action.as_str(), takeover = media.takeover, "Supervisor action applied.");
+    let detail = json!({ "text": command.text, "takeover": media.takeover });
+    media.emit(|| {
+        json!({
+            "type": "SUPERVISOR_ACTION",
+            "supervisor": command.supervisor,
+            "action": action.as_str(),
+            "detail": detail,
+        })
+    });
+    let _ = command.reply.send(Ok(media.takeover));
+    supervisor::publish_audit(
+        state,
+        &entry.info,
+        &command.supervisor,
+        action.as_str(),
+        detail,
+    )
+    .await;
+}
+
+/// Yönetim API'sinin `Terminate` komutu: istemciye 4012 ile kapanış gönderilir.
+fn admin_terminate(trace_id: &str, out: &Outbound) -> EndReason {
+    info!(event = "SESSION_ADMIN_TERMINATED", trace_id = %trace_id, "Session terminated by admin.");
+    close_with_error(out, &SessionError::AdminTerminated);
+    EndReason::AdminTerminated
+}
+
+/// [YENİ]: Yönetim API'si `GET /admin/sessions/{id}` için döngünün anlık durumu.
+fn inspect_session(ctx: &SessionContext, media: &SessionMedia) -> serde_json::Value {
+    json!({
+        "pipeline": if media.paused { "paused" } else { "running" },
+        "muted": media.muted,
+        "takeover": media.takeover,
+        "consent": media.consent,
+        "effective": ctx.effective,
+        "requested_language": ctx.requested.language,
+        "client_format": media.transcoder.client_format(),
+        "stats": media.stats.summary(),
+    })
+}
+
+/// [YENİ]: Sınırı aşan parça atılır ve istemciye bildirilir; ihlal sayısı sınırı geçerse oturum kapatılır.
+fn flow_violation(
+    violation: Violation,
+    state: &AppState,
+    ctx: &SessionContext,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) -> LoopAction {
+    media.stats.inbound_violations += 1;
+    state
+        .metrics
+        .incr(INBOUND_VIOLATIONS, &[("reason", violation.as_str())]);
+    let abusive = media.flow.record();
+    warn!(
+        event = "INBOUND_AUDIO_VIOLATION",
+        trace_id = %ctx.trace_id,
+        reason = violation.as_str(),
+        violations = media.flow.violations,
+        "Inbound audio chunk rejected."
+    );
+    if abusive {
+        close_with_error(out, &SessionError::PolicyViolation);
+        return LoopAction::Close(EndReason::PolicyViolation);
+    }
+    let status_json = json!({
+        "type": "FLOW_VIOLATION",
+        "reason": violation.as_str(),
+        "violations": media.flow.violations,
+        "limit": media.flow.max_violations,
+    })
+    .to_string();
+    delivered(out.send(RespData::StatusUpdate(status_json)))
+}
+
+/// [YENİ]: Günlük ses kotası oturum içinde dolduğunda istemci bilgilendirilir ve oturum kapatılır.
+fn quota_exhausted(
+    denied: QuotaDenied,
+    state: &AppState,
+    ctx: &SessionContext,
+    out: &Outbound,
+) -> LoopAction {
+    state.metrics.incr(
+        QUOTA_REJECTIONS,
+        &[
+            ("scope", denied.scope.as_str()),
+            ("limit", denied.limit.as_str()),
+        ],
+    );
+    warn!(
+        event = "SESSION_QUOTA_EXHAUSTED",
+        trace_id = %ctx.trace_id,
+        scope = denied.scope.as_str(),
+        limit = denied.limit.as_str(),
+        "Session closed by quota."
+    );
+    let mut status = json!(denied);
+    status["type"] = json!("QUOTA_EXCEEDED");
+    out.send(RespData::StatusUpdate(status.to_string()));
+    close_with_error(out, &SessionError::QuotaExceeded(denied));
+    LoopAction::Close(EndReason::QuotaExceeded)
+}
+
+async fn send_control_error(out: &Outbound, code: i32, reason: String) {
+    let status_json =
+        json!({ "type": "CONTROL_ERROR", "event": code, "reason": reason }).to_string();
+    out.send(RespData::StatusUpdate(status_json));
+}
+
+/// [YENİ]: Yeni istemci konfigürasyonunu çözer, aktif olanla karşılaştırır ve gerekirse
+/// pipeline'ı aynı session_id/trace ile yeniden kurar. Diyalog geçmişi dialog servisinde
+/// session_id ile tutulduğu için korunur. Yeni pipeline kurulamazsa mevcut olan çalışmaya devam eder.
+async fn reconfigure(
+    next: SessionConfig,
+    live_config: &crate::config::AppConfig,
+    ctx: &mut SessionContext,
+    pipeline: &mut PipelineHandle,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) {
+    let effective = match profile::resolve(live_config, ctx.profile_name.as_deref(), &next) {
+        Ok(effective) => effective,
+        Err(reason) => {
+            warn!(event = "SESSION_RECONFIGURE_REJECTED", trace_id = %ctx.trace_id, reason = %reason, "Session reconfiguration rejected.");
+            let status_json = json!({ "type": "CONFIG_REJECTED", "reason": reason }).to_string();
+            out.send(RespData::StatusUpdate(status_json));
+            return;
+        }
+    };
+
+    let changed = ctx.effective.changed_fields(&effective);
+    if changed.is_empty() {
+        ctx.requested = next;
+        let status_json = json!({ "type": "SESSION_RECONFIGURED", "changed": changed, "effective": &ctx.effective }).to_string();
+        out.send(RespData::StatusUpdate(status_json));
+        return;
+    }
+
+    let previous = std::mem::replace(&mut ctx.effective, effective);
+    match start_pipeline(live_config, ctx).await {
+        Ok(next_pipeline) => {
+            std::mem::replace(pipeline, next_pipeline).stop();
+            // Bekleyen hata sonrası yeniden kurulumun yerini yeni pipeline alır
+            media.restart_at = None;
+            ctx.requested = next;
+            if previous.sample_rate != ctx.effective.sample_rate {
+                media
+                    .transcoder
+                    .set_pipeline_output_rate(ctx.effective.sample_rate);
+                media.playback = PlaybackTracker::new(ctx.effective.sample_rate);
+            }
+            // Eski pipeline'ın yarım kalan TTS sesi istemcide çalmaya devam etmesin.
+            media.transcoder.reset_outbound();
+            media.playback.clear();
+            if let Some(vad) = media.vad.as_mut() {
+                vad.reset();
+            }
+            out.send(RespData::ClearAudioBuffer(true));
+            media.clear_recorded_ai();
+
+            info!(event = "SESSION_RECONFIGURED", trace_id = %ctx.trace_id, changed = ?changed, "Pipeline restarted with new session config.");
+            let status_json = json!({ "type": "SESSION_RECONFIGURED", "changed": changed, "effective": &ctx.effective }).to_string();
+            out.send(RespData::StatusUpdate(status_json));
+        }
+        Err(e) => {
+            ctx.effective = previous;
+            error!(event = "SESSION_RECONFIGURE_FAIL", trace_id = %ctx.trace_id, error = %e, "Pipeline restart failed. Keeping the current pipeline.");
+            let status_json = json!({ "type": "CONFIG_REJECTED", "reason": format!("pipeline restart failed: {}", e) }).to_string();
+            out.send(RespData::StatusUpdate(status_json));
+        }
+    }
+}
+
+/// [YENİ]: VAD durum değişimleri -> konuşma sonu, barge-in ve istemci bildirimi.
+async fn handle_vad_event(
+    vad_event: VadEvent,
+    pipeline: &PipelineHandle,
+    ctx: &SessionContext,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) {
+    let state = match vad_event {
+        VadEvent::SpeechStarted => {
+            if media.barge_in && media.playback.is_playing() {
+                info!(event = "VAD_BARGE_IN", trace_id = %ctx.trace_id, "User speech during TTS playback. Interrupting pipeline.");
+                let _ = pipeline.interrupt_tx.try_send(());
+                media.playback.clear();
+            }
+            "speech"
+        }
+        VadEvent::SpeechEnded => {
+            let _ = pipeline
+                .input_tx
+                .send(PipelineInputEvent::Audio(vec![]))
+                .await;
+            "silence"
+        }
+    };
+    if media.report_vad {
+        let status_json = json!({ "type": "VAD", "state": state }).to_string();
+        out.send(RespData::StatusUpdate(status_json));
     }
 }
 
 async fn process_ai_event(
     ai_event: Option<PipelineEvent>,
+    interrupt_tx: &mpsc::Sender<()>,
     state: &Arc<AppState>,
     ctx: &SessionContext,
-    socket: &mut WebSocket,
-) -> bool {
+    out: &Outbound,
+    media: &mut SessionMedia,
+) -> LoopAction {
     match ai_event {
         Some(PipelineEvent::AcousticMoodShifted {
             session_id: evt_sess_id,
@@ -269,17 +1613,50 @@ async fn process_ai_event(
                     .publish_protobuf("acoustic.mood.shifted", buf)
                     .await;
             }
-            let status_json = json!({ "type": "MOOD_SHIFT", "arousal_shift": arousal_shift, "new_mood": current_mood }).to_string();
-            send_ws_response(socket, RespData::StatusUpdate(status_json)).await;
-            true
+            let status = json!({ "type": "MOOD_SHIFT", "arousal_shift": arousal_shift, "new_mood": current_mood });
+            media.emit(|| status.clone());
+            out.send(RespData::StatusUpdate(status.to_string()));
+            LoopAction::Continue
         }
         Some(PipelineEvent::Audio(chunk)) => {
-            send_ws_response(socket, RespData::AudioResponse(chunk)).await
+            media.playback.on_audio_sent(chunk.len());
+            media
+                .monitor
+                .audio(monitor::Channel::Agent, &chunk, ctx.effective.sample_rate);
+            // Devralma sırasında kesilmeden önce üretilmiş AI sesi ve fısıltıya verilen yanıt
+            // kullanıcıya gitmez
+            if media.takeover || media.hint_reply.mutes(Instant::now()) {
+                return LoopAction::Continue;
+            }
+            media.capture(Track::Ai, &chunk, ctx.effective.sample_rate);
+            media.stats.audio_out_secs +=
+                SessionStats::pcm_seconds(chunk.len(), ctx.effective.sample_rate);
+            send_audio(chunk, ctx, media, out)
         }
         Some(PipelineEvent::ClearBuffer) => {
-            send_ws_response(socket, RespData::ClearAudioBuffer(true)).await
+            media.emit(|| json!({ "type": "CLEAR_AGENT_AUDIO" }));
+            media.clear_recorded_ai();
+            media.transcoder.reset_outbound();
+            media.playback.clear();
+            delivered(out.send(RespData::ClearAudioBuffer(true)))
         }
         Some(PipelineEvent::Transcript(td)) => {
+            if media
+                .hint_reply
+                .hides_transcript(&td.sender, td.is_final, Instant::now())
+            {
+                if td.is_final {
+                    // Yanıt bitti; henüz seslendirilmemiş kısmı da arayana gitmesin
+                    let _ = interrupt_tx.try_send(());
+                }
+                media.emit(
+                    || json!({ "type": "HINT_REPLY", "text": td.text, "is_final": td.is_final }),
+                );
+                return LoopAction::Continue;
+            }
+            if td.is_final {
+                *media.stats.turns.entry(td.sender.clone()).or_insert(0) += 1;
+            }
             let mapped_words: Vec<WordData> = td
                 .words
                 .into_iter()
@@ -302,64 +1679,178 @@ async fn process_ai_event(
                 speaker_vec: td.speaker_vec,
                 words: mapped_words,
             };
-            send_ws_response(socket, RespData::Transcript(t_event)).await
+            if media.consent.allows_processing() {
+                if let Some(shared) = media.transcript.as_ref() {
+                    transcript::lock(shared).observe(&t_event);
+                }
+                if let Some(captions) = media.captions.as_mut() {
+                    captions.observe(&t_event);
+                }
+            }
+            if let Some(exporter) = media.exporter.as_mut() {
+                exporter.transcript(json!({
+                    "sender": t_event.sender,
+                    "text": t_event.text,
+                    "is_final": t_event.is_final,
+                    "emotion": t_event.emotion,
+                    "gender": t_event.gender,
+                    "arousal": t_event.arousal,
+                    "valence": t_event.valence,
+                    "speaker_id": t_event.speaker_id,
+                    "words": t_event
+                        .words
+                        .iter()
+                        .map(|w| json!({ "word": w.word, "start": w.start, "end": w.end, "probability": w.probability }))
+                        .collect::<Vec<_>>(),
+                }));
+            }
+            media.emit(|| {
+                json!({
+                    "type": "TRANSCRIPT",
+                    "sender": t_event.sender,
+                    "text": t_event.text,
+                    "is_final": t_event.is_final,
+                    "emotion": t_event.emotion,
+                })
+            });
+            delivered(out.send(RespData::Transcript(t_event)))
+        }
+        None => LoopAction::Close(EndReason::PipelineCompleted),
+    }
+}
+
+/// İstemciye yazma başarısızsa bağlantı kopmuş sayılır.
+fn delivered(sent: bool) -> LoopAction {
+    if sent {
+        LoopAction::Continue
+    } else {
+        LoopAction::Close(EndReason::ClientDisconnected)
+    }
+}
+
+/// [YENİ]: Pipeline hatasını istemciye bildirir; yeniden denenebilirse ve izin varsa
+/// WebSocket'i koparmadan yeniden kurulumu geri çekilme süresi sonrasına planlar.
+/// Bekleme oturum döngüsünde ayrı bir kolda geçer; ping, kontrol ve süpervizör komutları işlenmeye
+/// devam eder. `false`: oturum kapatılmalı.
+fn schedule_pipeline_restart(
+    failure: PipelineFailure,
+    live_config: &crate::config::AppConfig,
+    ctx: &SessionContext,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) -> bool {
+    let limits = &live_config.session;
+    let will_restart = failure.retryable
+        && limits.pipeline_auto_restart
+        && media.stats.pipeline_restarts < limits.pipeline_max_restarts;
+    error!(event = "PIPELINE_FAILURE", trace_id = %ctx.trace_id, category = ?failure.category, grpc_code = ?failure.grpc_code, retryable = failure.retryable, restarting = will_restart, error = %failure.message, "Pipeline stopped.");
+
+    let status_json = json!({
+        "type": "PIPELINE_ERROR",
+        "category": failure.category,
+        "retryable": failure.retryable,
+        "restarting": will_restart,
+    })
+    .to_string();
+    out.send(RespData::StatusUpdate(status_json));
+
+    if !will_restart {
+        close_with_error(out, &SessionError::PipelineFailed);
+        return false;
+    }
+    media.stats.pipeline_restarts += 1;
+    let backoff = Duration::from_millis(
+        limits.pipeline_restart_backoff_ms * media.stats.pipeline_restarts as u64,
+    );
+    media.restart_at = Some(tokio::time::Instant::now() + backoff);
+    // Eski pipeline'ın yarım kalan TTS sesi bekleme boyunca çalmaya devam etmesin
+    media.transcoder.reset_outbound();
+    media.playback.clear();
+    if let Some(vad) = media.vad.as_mut() {
+        vad.reset();
+    }
+    out.send(RespData::ClearAudioBuffer(true));
+    media.clear_recorded_ai();
+    true
+}
+
+/// Planlanan yeniden kurulum zamanı geldiğinde pipeline'ı kurar. `false`: oturum kapatılmalı.
+async fn restart_pipeline(
+    live_config: &crate::config::AppConfig,
+    ctx: &SessionContext,
+    pipeline: &mut PipelineHandle,
+    media: &mut SessionMedia,
+    out: &Outbound,
+) -> bool {
+    let attempt = media.stats.pipeline_restarts;
+    match start_pipeline(live_config, ctx).await {
+        Ok(next_pipeline) => {
+            *pipeline = next_pipeline;
+            info!(event = "PIPELINE_RESTARTED", trace_id = %ctx.trace_id, attempt = attempt, "Pipeline restarted after retryable failure.");
+            let status_json =
+                json!({ "type": "PIPELINE_RESTARTED", "attempt": attempt }).to_string();
+            out.send(RespData::StatusUpdate(status_json));
+            true
+        }
+        Err(e) => {
+            error!(event = "PIPELINE_RESTART_FAIL", trace_id = %ctx.trace_id, attempt = attempt, error = %e, "Pipeline could not be restarted.");
+            close_with_error(out, &SessionError::PipelineFailed);
+            false
         }
-        None => false,
     }
 }
 
 async fn process_cognitive_map(
     cog_event_res: Result<CognitiveMapUpdatedEvent, tokio::sync::broadcast::error::RecvError>,
     trace_id: &str,
-    socket: &mut WebSocket,
+    out: &Outbound,
 ) {
     if let Ok(cog_event) = cog_event_res {
         if cog_event.trace_id == trace_id {
-            send_ws_response(socket, RespData::CognitiveMap(cog_event)).await;
+            out.send(RespData::CognitiveMap(cog_event));
         }
     }
 }
 
-async fn send_ws_response(socket: &mut WebSocket, data: RespData) -> bool {
-    let resp = StreamSessionResponse { data: Some(data) };
-    let mut buf = Vec::new();
-    if resp.encode(&mut buf).is_ok() {
-        return socket.send(Message::Binary(buf)).await.is_ok();
+fn build_sdk_config(app_cfg: &crate::config::AppConfig, effective: &EffectiveSession) -> SdkConfig {
+    SdkConfig {
+        stt_gateway_url: app_cfg.upstream.stt_gateway_url.clone(),
+        dialog_service_url: app_cfg.upstream.dialog_service_url.clone(),
+        tts_gateway_url: app_cfg.upstream.tts_gateway_url.clone(),
+        tls_ca_path: app_cfg.tls.ca_path.clone(),
+        tls_cert_path: app_cfg.tls.cert_path.clone(),
+        tls_key_path: app_cfg.tls.key_path.clone(),
+        language_code: effective.language.clone(),
+        system_prompt_id: effective.system_prompt_id.clone(),
+        tts_voice_id: effective.voice_id.clone(),
+        tts_sample_rate: effective.sample_rate,
+        edge_mode: effective.edge_mode,
+        listen_only_mode: effective.listen_only_mode,
+        speak_only_mode: effective.speak_only_mode,
+        chat_only_mode: effective.chat_only_mode,
     }
-    true
 }
 
-fn build_sdk_config(app_cfg: &crate::config::AppConfig, sess_cfg: &SessionConfig) -> SdkConfig {
-    let default_voice =
-        std::env::var("TTS_DEFAULT_VOICE_ID").unwrap_or_else(|_| "omnivoice:female".to_string());
-    SdkConfig {
-        stt_gateway_url: app_cfg.stt_gateway_url.clone(),
-        dialog_service_url: app_cfg.dialog_service_url.clone(),
-        tts_gateway_url: app_cfg.tts_gateway_url.clone(),
-        tls_ca_path: app_cfg.tls_ca_path.clone(),
-        tls_cert_path: app_cfg.tls_cert_path.clone(),
-        tls_key_path: app_cfg.tls_key_path.clone(),
-        language_code: sess_cfg.language.clone(),
-        system_prompt_id: if sess_cfg.system_prompt_id.is_empty() {
-            "PROMPT_SYSTEM_DEFAULT".into()
-        } else {
-            sess_cfg.system_prompt_id.clone()
-        },
-        tts_voice_id: if sess_cfg.tts_voice_id.is_empty() {
-            default_voice
-        } else {
-            sess_cfg.tts_voice_id.clone()
-        },
-        tts_sample_rate: if sess_cfg.sample_rate > 0 {
-            sess_cfg.sample_rate
-        } else {
-            16000
-        },
-        edge_mode: sess_cfg.edge_mode,
-        listen_only_mode: sess_cfg.listen_only_mode,
-        speak_only_mode: sess_cfg.speak_only_mode,
-        chat_only_mode: sess_cfg.chat_only_mode,
-    }
+async fn start_pipeline(
+    app_cfg: &crate::config::AppConfig,
+    ctx: &SessionContext,
+) -> Result<PipelineHandle, String> {
+    let orchestrator = PipelineOrchestrator::new(build_sdk_config(app_cfg, &ctx.effective))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let (input_tx, rx_input_rx) =
+        mpsc::channel::<PipelineInputEvent>(app_cfg.session.input_channel_capacity);
+    let (tx_out_tx, output_rx) = mpsc::channel(app_cfg.session.output_channel_capacity);
+    let (interrupt_tx, interrupt_rx) = mpsc::channel(app_cfg.session.interrupt_channel_capacity);
+
+    let task = spawn_orchestrator(orchestrator, ctx, rx_input_rx, tx_out_tx, interrupt_rx);
+    Ok(PipelineHandle {
+        input_tx,
+        interrupt_tx,
+        output_rx,
+        task,
+    })
 }
 
 fn spawn_orchestrator(
@@ -368,7 +1859,7 @@ fn spawn_orchestrator(
     rx_input_rx: mpsc::Receiver<PipelineInputEvent>,
     tx_out_tx: mpsc::Sender<PipelineEvent>,
     interrupt_rx: mpsc::Receiver<()>,
-) {
+) -> JoinHandle<Result<(), PipelineFailure>> {
     let tr_id = ctx.trace_id.clone();
     let sp_id = ctx.span_id.clone();
     let ten_id = ctx.tenant_id.clone();
@@ -390,24 +1881,34 @@ fn spawn_orchestrator(
             .await
         {
             error!(event = "PIPELINE_ERROR", trace_id = %tr_id, error = %e, "Pipeline fatal error.");
+            // Tipli hata yalnızca burada elde; gRPC durumu kaybolmadan sınıflandırılır
+            return Err(pipeline_error::classify(&*e));
         }
-    });
+        Ok(())
+    })
 }
 
-async fn publish_call_started(state: &Arc<AppState>, ctx: &SessionContext) {
+async fn publish_call_started(state: &Arc<AppState>, ctx: &SessionContext, consent: &Consent) {
+    let now = chrono::Utc::now();
+    // Dinleyici ayarı yeniden başlatma gerektirir; açılıştaki konfigürasyon geçerlidir
+    let scheme = if state.config.server.tls.enabled {
+        "wss"
+    } else {
+        "ws"
+    };
     let call_started = sentiric_contracts::sentiric::event::v1::CallStartedEvent {
         event_type: "call.started".to_string(),
         trace_id: ctx.trace_id.clone(),
         call_id: ctx.session_id.clone(),
-        from_uri: "web-client".to_string(),
+        from_uri: format!("{}://{}", scheme, ctx.client.remote_addr),
         to_uri: "ai-pipeline".to_string(),
         timestamp: Some(prost_types::Timestamp {
-            seconds: chrono::Utc::now().timestamp(),
-            nanos: 0,
+            seconds: now.timestamp(),
+            nanos: now.timestamp_subsec_nanos() as i32,
         }),
         dialplan_resolution: None,
         media_info: Some(sentiric_contracts::sentiric::event::v1::MediaInfo {
-            caller_rtp_addr: "websocket".to_string(),
+            caller_rtp_addr: ctx.client.remote_addr.clone(),
             server_rtp_port: 0,
         }),
     };
@@ -418,18 +1919,40 @@ async fn publish_call_started(state: &Arc<AppState>, ctx: &SessionContext) {
             .publish_protobuf("call.started", buf)
             .await;
     }
+
+    // [YENİ]: CallStartedEvent onay durumu taşımadığı için ayrı JSON olayı yayınlanır.
+    let started = json!({
+        "event_type": "stream.session.started",
+        "trace_id": ctx.trace_id,
+        "call_id": ctx.session_id,
+        "tenant_id": ctx.tenant_id,
+        "timestamp": now.to_rfc3339(),
+        "profile": ctx.effective.profile,
+        "client": ctx.client,
+        "consent": consent,
+    });
+    state
+        .ghost_publisher
+        .publish_json("stream.session.started", started)
+        .await;
 }
 
-async fn publish_call_ended(state: &Arc<AppState>, ctx: &SessionContext) {
+async fn publish_call_ended(
+    state: &Arc<AppState>,
+    ctx: &SessionContext,
+    reason: EndReason,
+    stats: &SessionStats,
+) {
+    let now = chrono::Utc::now();
     let call_ended = sentiric_contracts::sentiric::event::v1::CallEndedEvent {
         event_type: "call.ended".to_string(),
         trace_id: ctx.trace_id.clone(),
         call_id: ctx.session_id.clone(),
         timestamp: Some(prost_types::Timestamp {
-            seconds: chrono::Utc::now().timestamp(),
-            nanos: 0,
+            seconds: now.timestamp(),
+            nanos: now.timestamp_subsec_nanos() as i32,
         }),
-        reason: "client_disconnected".to_string(),
+        reason: reason.as_str().to_string(),
     };
     let mut buf = Vec::new();
     if call_ended.encode(&mut buf).is_ok() {
@@ -438,5 +1961,143 @@ async fn publish_call_ended(state: &Arc<AppState>, ctx: &SessionContext) {
             .publish_protobuf("call.ended", buf)
             .await;
     }
-    tracing::info!(event="WS_SESSION_CLOSED", trace_id=%ctx.trace_id, session_id=%ctx.session_id, "WebSocket session safely closed.");
+
+    // [YENİ]: Sözleşmedeki CallEndedEvent ek alan taşımadığı için oturum özeti ayrı JSON olayıdır.
+    let summary = json!({
+        "event_type": "stream.session.ended",
+        "trace_id": ctx.trace_id,
+        "call_id": ctx.session_id,
+        "tenant_id": ctx.tenant_id,
+        "timestamp": now.to_rfc3339(),
+        "reason": reason.as_str(),
+        "profile": ctx.effective.profile,
+        "language": ctx.effective.language,
+        "stats": stats.summary(),
+        "client": ctx.client,
+    });
+    state
+        .ghost_publisher
+        .publish_json("stream.session.ended", summary)
+        .await;
+
+    state
+        .metrics
+        .incr(SESSIONS_ENDED, &[("reason", reason.as_str())]);
+    tracing::info!(event="WS_SESSION_CLOSED", trace_id=%ctx.trace_id, session_id=%ctx.session_id, reason = reason.as_str(), duration_secs = stats.duration_secs(), "WebSocket session safely closed.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::QuotaLimits;
+
+    fn state() -> Arc<AppState> {
+        let mut config = AppConfig {
+            tenant_id: "default".into(),
+            ..Default::default()
+        };
+        config.auth.tokens = vec!["client-token".into()];
+        config.admission.max_active_sessions = 1;
+        config.limits.tenant = QuotaLimits {
+            sessions_per_minute: 1,
+            ..QuotaLimits::default()
+        };
+        config.limits.ip = QuotaLimits {
+            max_concurrent_sessions: 1,
+            ..QuotaLimits::default()
+        };
+        Arc::new(AppState::new(config))
+    }
+
+    #[tokio::test]
+    async fn rejected_token_consumes_no_admission_or_quota() {
+        let state = state();
+        let cfg = state.current_config();
+        for token in [None, Some("wrong-token")] {
+            for _ in 0..3 {
+                assert!(matches!(
+                    admit(&state, &cfg, token, "6.6.6.6".into()),
+                    Err(Rejected::Unauthorized)
+                ));
+            }
+        }
+        // Tenant'ın dakikada tek oturumu, aynı IP'den gelen geçerli kullanıcıya kalmıştır
+        let admitted = admit(&state, &cfg, Some("client-token"), "6.6.6.6".into())
+            .ok()
+            .expect("valid token is admitted");
+        assert!(admitted
+            .user_id
+            .as_deref()
+            .is_some_and(|u| u.starts_with("token-")));
+        assert!(matches!(
+            admit(&state, &cfg, Some("client-token"), "6.6.6.7".into()),
+            Err(Rejected::Overloaded(_))
+        ));
+        drop(admitted);
+        // Kabul bileti bırakıldı; dakikalık tenant kotasını ise geçerli oturum kullandı
+        assert!(matches!(
+            admit(&state, &cfg, Some("client-token"), "6.6.6.7".into()),
+            Err(Rejected::Quota(denied)) if denied.scope == Scope::Tenant
+        ));
+    }
+
+    #[tokio::test]
+    async fn admin_terminate_ends_the_registered_session() {
+        use axum::extract::{Path, State};
+        use axum::http::{header, HeaderMap, StatusCode};
+
+        let mut config = AppConfig {
+            tenant_id: "default".into(),
+            ..Default::default()
+        };
+        config.admin.tokens = vec!["admin-token".into()];
+        let state = Arc::new(AppState::new(config));
+        let out = socket::detached(4);
+        let info = SessionInfo {
+            session_id: "s-1".into(),
+            trace_id: "t-1".into(),
+            tenant_id: "default".into(),
+            user_id: "anonymous".into(),
+            profile: None,
+            client: ClientMeta::from_request(
+                "10.0.0.1:5000".parse().unwrap(),
+                &HeaderMap::new(),
+                &HashMap::new(),
+            ),
+        };
+        let (entry, mut commands) =
+            SessionEntry::new(info, out.clone(), SessionMonitor::new(4), None, "t".into());
+        let registration = state.sessions.register(entry);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer admin-token".parse().unwrap());
+        let terminate = |headers: HeaderMap| {
+            crate::server::admin::terminate_session(
+                State(state.clone()),
+                Path("s-1".to_string()),
+                headers,
+            )
+        };
+        assert_eq!(
+            terminate(HeaderMap::new()).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            terminate(headers.clone()).await.status(),
+            StatusCode::ACCEPTED
+        );
+
+        let Some(SessionCommand::Terminate) = commands.recv().await else {
+            panic!("terminate command not delivered");
+        };
+        assert_eq!(admin_terminate("t-1", &out), EndReason::AdminTerminated);
+        // Kapanış çerçevesi kuyrukta; sonrasında gönderim yapılmaz
+        assert!(!out.ping());
+
+        // Döngü bittikten sonra kayıt kalkar
+        drop(commands);
+        assert_eq!(terminate(headers.clone()).await.status(), StatusCode::GONE);
+        drop(registration);
+        assert_eq!(terminate(headers).await.status(), StatusCode::NOT_FOUND);
+    }
 }
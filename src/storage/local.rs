@@ -0,0 +1,82 @@
+// [YENİ]: Yerel dosya sistemi hedefi: `<directory>/<bucket>/<key>`. Yükleme sürerken veri
+// `.part` uzantılı dosyaya eklenir, tamamlanınca asıl ada taşınır.
+use super::StoreError;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt;
+
+pub struct LocalStore {
+    root: PathBuf,
+}
+
+pub struct LocalUpload {
+    file: File,
+    temp: PathBuf,
+    target: PathBuf,
+}
+
+impl LocalStore {
+    pub fn new(directory: &str, bucket: &str) -> Self {
+        Self {
+            root: Path::new(directory).join(bucket),
+        }
+    }
+
+    pub fn location(&self, key: &str) -> String {
+        format!("file://{}", self.path(key).display())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    pub async fn put(&self, key: &str, body: Vec<u8>) -> Result<(), StoreError> {
+        let mut upload = self.start_upload(key).await?;
+        upload.append(&body).await?;
+        upload.complete().await
+    }
+
+    pub async fn start_upload(&self, key: &str) -> Result<LocalUpload, StoreError> {
+        let target = self.path(key);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|e| io_error(parent, e))?;
+        }
+        let mut temp = target.clone().into_os_string();
+        temp.push(".part");
+        let temp = PathBuf::from(temp);
+        let file = File::create(&temp).await.map_err(|e| io_error(&temp, e))?;
+        Ok(LocalUpload { file, temp, target })
+    }
+}
+
+impl LocalUpload {
+    pub async fn append(&mut self, body: &[u8]) -> Result<(), StoreError> {
+        self.file
+            .write_all(body)
+            .await
+            .map_err(|e| io_error(&self.temp, e))
+    }
+
+    pub async fn complete(self) -> Result<(), StoreError> {
+        self.file
+            .sync_all()
+            .await
+            .map_err(|e| io_error(&self.temp, e))?;
+        fs::rename(&self.temp, &self.target)
+            .await
+            .map_err(|e| io_error(&self.target, e))
+    }
+
+    pub async fn abort(self) -> Result<(), StoreError> {
+        drop(self.file);
+        fs::remove_file(&self.temp)
+            .await
+            .map_err(|e| io_error(&self.temp, e))
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> StoreError {
+    StoreError::permanent(format!("'{}': {}", path.display(), e))
+}
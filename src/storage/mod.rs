@@ -0,0 +1,151 @@
+// [YENİ]: Nesne depolama hedefleri. Dışa aktarım yalnızca bu arayüzü görür; yerel dosya sistemi
+// ve S3 uyumlu depolama aynı parça parça yükleme akışını (başlat / parça / tamamla / iptal) uygular.
+pub mod local;
+pub mod s3;
+
+use crate::config::{ExportConfig, ExportSink};
+use std::fmt;
+use std::time::Duration;
+
+/// En uzun yeniden deneme beklemesi
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub struct StoreError {
+    pub message: String,
+    /// Ağ hatası, 5xx, 429 gibi geçici durumlar
+    pub retryable: bool,
+}
+
+impl StoreError {
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: false,
+        }
+    }
+
+    pub fn transient(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: true,
+        }
+    }
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// `attempt` 0'dan başlar: backoff, 2×backoff, 4×backoff ...
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(1 << attempt.min(16))
+            .min(MAX_BACKOFF)
+    }
+}
+
+/// Devam eden çok parçalı yükleme.
+pub enum Upload {
+    Local(local::LocalUpload),
+    S3(s3::S3Upload),
+}
+
+pub enum ObjectStore {
+    Local(local::LocalStore),
+    S3(s3::S3Store),
+}
+
+impl ObjectStore {
+    pub fn from_config(cfg: &ExportConfig) -> Result<Self, String> {
+        let retry = RetryPolicy {
+            max_retries: cfg.max_retries,
+            backoff: Duration::from_millis(cfg.retry_backoff_ms),
+        };
+        match cfg.sink {
+            ExportSink::Local => Ok(Self::Local(local::LocalStore::new(
+                &cfg.directory,
+                &cfg.bucket,
+            ))),
+            ExportSink::S3 => s3::S3Store::new(&cfg.s3, &cfg.bucket, retry).map(Self::S3),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Local(_) => "local",
+            Self::S3(_) => "s3",
+        }
+    }
+
+    /// Nesnenin kullanıcıya gösterilecek adresi (`file://...` veya `s3://kova/anahtar`).
+    pub fn location(&self, key: &str) -> String {
+        match self {
+            Self::Local(store) => store.location(key),
+            Self::S3(store) => store.location(key),
+        }
+    }
+
+    /// Küçük nesneyi tek istekte yazar.
+    pub async fn put(
+        &self,
+        key: &str,
+        body: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), StoreError> {
+        match self {
+            Self::Local(store) => store.put(key, body).await,
+            Self::S3(store) => store.put(key, body, content_type).await,
+        }
+    }
+
+    pub async fn start_upload(&self, key: &str, content_type: &str) -> Result<Upload, StoreError> {
+        match self {
+            Self::Local(store) => store.start_upload(key).await.map(Upload::Local),
+            Self::S3(store) => store.start_upload(key, content_type).await.map(Upload::S3),
+        }
+    }
+
+    pub async fn upload_part(
+        &self,
+        key: &str,
+        upload: &mut Upload,
+        body: Vec<u8>,
+    ) -> Result<(), StoreError> {
+        match (self, upload) {
+            (Self::Local(_), Upload::Local(upload)) => upload.append(&body).await,
+            (Self::S3(store), Upload::S3(upload)) => store.upload_part(key, upload, body).await,
+            _ => Err(StoreError::permanent(
+                "upload does not belong to this store",
+            )),
+        }
+    }
+
+    pub async fn complete_upload(&self, key: &str, upload: Upload) -> Result<(), StoreError> {
+        match (self, upload) {
+            (Self::Local(_), Upload::Local(upload)) => upload.complete().await,
+            (Self::S3(store), Upload::S3(upload)) => store.complete_upload(key, upload).await,
+            _ => Err(StoreError::permanent(
+                "upload does not belong to this store",
+            )),
+        }
+    }
+
+    /// Yarım kalan yüklemeyi temizler; hata yalnızca raporlanır.
+    pub async fn abort_upload(&self, key: &str, upload: Upload) -> Result<(), StoreError> {
+        match (self, upload) {
+            (Self::Local(_), Upload::Local(upload)) => upload.abort().await,
+            (Self::S3(store), Upload::S3(upload)) => store.abort_upload(key, upload).await,
+            _ => Ok(()),
+        }
+    }
+}